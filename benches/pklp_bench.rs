@@ -1,4 +1,5 @@
 #[path = "../src/pklp.rs"]
+#[allow(unused_imports)]
 mod pklp;
 use pklp::{parse_string, str_to_json};
 
@@ -8,7 +8,7 @@ fn test_parser_files(dir: &str) -> (usize, usize, f64) {
     let mut counts = Vec::new();
     for path in std::fs::read_dir(dir).unwrap() {
         paths.push(path.unwrap().path().to_str().unwrap().to_owned());
-        files.push(std::fs::read_to_string(&paths.last().as_ref().unwrap()).unwrap());
+        files.push(std::fs::read_to_string(paths.last().as_ref().unwrap()).unwrap());
         counts.push(0);
     }
     
@@ -23,7 +23,7 @@ fn test_parser_files(dir: &str) -> (usize, usize, f64) {
 
     let parsed = counts.iter().sum();
     let mut total = 0;
-    for f in &files { total += f.matches("PokerStars Hand").count(); }
+    for f in &files { total += pklp::detect_site(f).map_or(0, |site| site.count_hands(f)); }
 
     (parsed, total, (elapsed.as_micros() as u64) as f64 / 1000000.0)
 }
@@ -16,13 +16,13 @@ pub fn path_to_json(path: &str) -> String {
 #[allow(dead_code)]
 pub fn path_to_json_file(path: &str, output_path: &str) {
     let data = std::fs::read_to_string(path).unwrap();
-    std::fs::write(output_path, &str_to_json(&data)).unwrap();
+    std::fs::write(output_path, str_to_json(&data)).unwrap();
 }
 
 #[allow(dead_code)]
 pub fn strs_to_json(data: &[&str]) -> String {
     let strings = data.par_iter()
-        .map(|f| str_to_json(*f))
+        .map(|f| str_to_json(f))
         .collect::<Vec<String>>();
     json_join(&strings)
 }
@@ -30,14 +30,14 @@ pub fn strs_to_json(data: &[&str]) -> String {
 #[allow(dead_code)]
 pub fn paths_to_json(paths: &[&str]) -> String {
     let strings = paths.par_iter()
-        .map(|f| path_to_json(*f))
+        .map(|f| path_to_json(f))
         .collect::<Vec<String>>();
     json_join(&strings)
 }
 
 #[allow(dead_code)]
 pub fn paths_to_json_file(paths: &[&str], output_path: &str) {
-    std::fs::write(output_path, &paths_to_json(paths)).unwrap();
+    std::fs::write(output_path, paths_to_json(paths)).unwrap();
 }
 
 fn json_join(json: &[String]) -> String {
@@ -90,6 +90,7 @@ impl<T> std::ops::Index<Span> for [T] {
 }
 
 
+#[allow(dead_code)]
 trait StringOps<'a> {
     fn str(&self) -> &'a str;
     #[inline] fn prefix(&self, end: char)                 -> &'a str { &self.str()[..self.str().find(end).unwrap()] }
@@ -114,13 +115,23 @@ impl<'a> StringOps<'a> for &'a str {
 
 use stackvector::StackVec;
 
-pub type Cards = StackVec<[Card; 5]>;
-pub type PlayerVec = StackVec<[Player; 8]>;
+pub type Cards = StackVec<[Card; 7]>;
+pub type PlayerVec = StackVec<[Player; 10]>;
 pub type ActionVec = Vec<Action>;
+pub type PlayerCardsVec = Vec<(Span, Cards)>;
 pub type Float = f32;
 
+// Symbol of amounts written without a currency (play money and tournament chips)
+pub const NO_CURRENCY: char = '\u{a4}';
+
+// Side pots are numbered from 1 as the log writes them, "from side pot-2" is Side(Some(2)),
+// None when the log does not say which side pot
 #[derive(Debug, Clone, Copy)]
-pub enum Pot { Main, Side }
+pub enum Pot { Main, Side(Option<u8>) }
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HiLo { High, Low }
 
 
 #[derive(Clone, Copy)]
@@ -132,7 +143,7 @@ pub struct Currency{pub symbol: char, pub amount: Float}
 
 
 #[derive(Debug, Clone, Copy)]
-pub struct Table{pub name: Span, pub max_players: u8, pub button: u8}
+pub struct Table{pub name: Span, pub max_players: u8, pub button: u8, pub play_money: bool}
 
 
 #[derive(Debug, Clone, Copy)]
@@ -141,6 +152,8 @@ pub enum BlindType {
     Small,
     Big,
     SmallAndBig,
+    Ante,
+    BringIn,
 }
 
 
@@ -153,6 +166,7 @@ pub enum Event {
     Leave,
     Sitout,
     NotShow,
+    EvCashOut,
 }
 
 
@@ -164,6 +178,8 @@ pub enum Play {
     Check,
     Raise,
     Stand,
+    #[allow(dead_code)]
+    #[deprecated(note = "discards are parsed as Action::Discard, which also records the count and the cards")]
     Discard,
 }
 
@@ -174,6 +190,12 @@ pub enum StreetType {
     Flop,
     Turn,
     River,
+    Draw,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
     Showdown,
 }
 
@@ -186,10 +208,14 @@ pub enum Action {
     Blind(BlindType, Span, Currency),
     ShowHand(Span, Cards),
     Fold(Span, Option<Cards>),
+    Discard(Span, u8, Option<Cards>),
     Say(Span, Span),
     CashOut(Span, Currency, Currency),
+    PaysCashOutRisk(Span, Currency),
     UncalledBetReturned(Span, Currency),
-    CollectedPot(Pot, Span, Currency),
+    CollectedPot(Pot, Span, Currency, Option<HiLo>),
+    // Lines of an action section that are not actions (tournament results, table notices)
+    Unknown(Span),
 }
 
 
@@ -221,9 +247,13 @@ pub struct Player {
 
 #[derive(Debug)]
 pub struct HandInfo {
+    pub site_name: &'static str,
     pub site: Span,
     pub hand: Span,
     pub id: Span,
+    pub is_zoom: bool,
+    pub home_game_club: Option<Span>,
+    pub mixed_game: Option<Span>,
     pub game_type: Span,
     pub buy_in_min: Currency,
     pub buy_in_max: Currency,
@@ -252,17 +282,44 @@ pub struct Street {
     pub index: u8,
     pub cards: Option<Cards>,
     pub new_card: Option<Card>,
+    pub dealt_to: Vec<(Span, Cards, Option<Cards>)>,
+    pub up_cards: PlayerCardsVec,
+    pub down_cards: PlayerCardsVec,
     pub actions: ActionVec,
 }
 
 
+#[derive(Debug, Clone, Copy)]
+pub struct Award {
+    pub amount: Currency,
+    pub hi_lo: Option<HiLo>,
+    pub hand: Option<Span>,
+}
+
+
+#[derive(Debug)]
+pub struct SeatSummary {
+    pub seat: u8,
+    pub name: Span,
+    pub cards: Option<Cards>,
+    pub mucked: bool,
+    pub won: Vec<Award>,
+}
+
+
 #[derive(Debug)]
 pub struct Summary {
     pub pot: Currency,
     pub main_pot: Currency,
+    // Total of the side pots
     pub side_pot: Currency,
+    // Side pot-1, Side pot-2 of the summary line, empty when the site does not list them
+    pub side_pots: Vec<Currency>,
     pub rake: Option<Currency>,
+    pub jackpot: Option<Currency>,
+    pub fees: Vec<(Span, Currency)>,
     pub boards: Vec<Cards>,
+    pub seats: Vec<SeatSummary>,
 }
 
 
@@ -287,6 +344,20 @@ impl std::fmt::Debug for Card {
     }
 }
 
+// Hand history notation: Ah, Td, 2c
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let n = b"?A23456789TJQKA"[self.n as usize % 15] as char;
+        let suit_c = match self.suit {
+            Suit::Heart => 'h',
+            Suit::Club => 'c',
+            Suit::Diamond => 'd',
+            Suit::Spade => 's',
+        };
+        write!(f, "{}{}", n, suit_c)
+    }
+}
+
 impl std::fmt::Debug for Currency {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}{}", self.symbol, self.amount)
@@ -295,14 +366,224 @@ impl std::fmt::Debug for Currency {
 
 //endregion
 
+//region Hand
+
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+impl HoleCards {
+    // Only the hero is dealt cards in regular hand histories, logs like Pluribus list every player
+    pub fn hero(&self) -> Option<Span> {
+        match self.dealt_to.as_slice() {
+            [(name, _)] => Some(*name),
+            _ => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // Seat order starting after the button, the order of blinds and postflop actions
+    pub fn players_after_button(&self) -> Vec<&Player> {
+        let mut players = self.header.players.iter().collect::<Vec<_>>();
+        players.sort_by_key(|p| p.seat);
+        let first = players.iter().position(|p| p.seat > self.header.table.button).unwrap_or(0);
+        players.rotate_left(first);
+        players
+    }
+
+    pub fn hero(&self) -> Option<Span> {
+        self.hole_cards.hero().or_else(|| {
+            // Stud hands deal the down cards of the hero on 3rd street
+            match self.streets.first().map(|s| s.down_cards.as_slice()) {
+                Some([(name, _)]) => Some(*name),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item=&Action> {
+        self.header.actions.iter()
+            .chain(self.hole_cards.actions.iter())
+            .chain(self.streets.iter().flat_map(|s| s.actions.iter()))
+    }
+
+    pub fn known_hole_cards<'d>(&self, data: &'d str) -> HashMap<&'d str, Cards> {
+        self.seen_hole_cards(data).map(|(name, cards)| (&data[name], cards)).collect()
+    }
+
+    pub fn known_hole_cards_of_seat(&self, seat: u8, data: &str) -> Option<Cards> {
+        let player = self.header.players.iter().find(|p| p.seat == seat)?;
+        self.seen_hole_cards(data).filter(|(name, _)| data[*name] == data[player.name]).last().map(|(_, cards)| cards)
+    }
+
+    // Every place hole cards show up, later ones replace earlier ones of the same player
+    fn seen_hole_cards<'h>(&'h self, data: &'h str) -> impl Iterator<Item=(Span, Cards)> + 'h {
+        let dealt = self.hole_cards.dealt_to.iter().map(|(name, cards)| (*name, cards.clone()));
+
+        // Draws replace cards of the hero, stud deals them street by street. 
+        // Opponents in stud only have their up cards listed which are not hole cards.
+        let has_down_cards = move |name: &Span| self.streets.iter()
+            .flat_map(|s| &s.down_cards)
+            .any(|(n, _)| data[*n] == data[*name]);
+        let streets = self.streets.iter()
+            .flat_map(|street| street.dealt_to.iter().map(move |d| (street, d)))
+            .filter(move |(street, (name, _, _))| matches!(street.t, StreetType::Draw) || has_down_cards(name))
+            .map(|(_, (name, cards, new_cards))| {
+                let mut all = cards.clone();
+                all.extend(new_cards.iter().flat_map(|c| c.iter().copied()));
+                (*name, all)
+            });
+
+        let shown = self.actions().filter_map(|a| match a {
+            Action::ShowHand(name, cards) | Action::Fold(name, Some(cards)) => Some((*name, cards.clone())),
+            _ => None,
+        });
+        let summary = self.summary.seats.iter().filter_map(|seat| seat.cards.clone().map(|cards| (seat.name, cards)));
+
+        dealt.chain(streets).chain(shown).chain(summary)
+    }
+}
+
+//endregion
+
 //region Parser
 
 pub fn parse_string(s: &str) -> Vec<Hand> {
-    let mut parser = Parser::new(s);
-    parser.parse();
-    parser.hands
+    // Files without a known header near the start are read as PokerStars
+    detect_site(s).unwrap_or(&PokerStars).parse(s)
 }
 
+#[allow(dead_code)]
+pub trait SiteParser: Sync {
+    fn name(&self) -> &'static str;
+    // Whether the first lines of a hand history were written by this site
+    fn detect(&self, head: &str) -> bool;
+    fn parse(&self, data: &str) -> Vec<Hand>;
+    fn count_hands(&self, data: &str) -> usize;
+}
+
+const SITE_PARSERS: &[&dyn SiteParser] = &[&PokerStars, &GGPoker, &PartyPoker, &Poker888, &Winamax, &IPoker, &Acr, &Phh, &Acpc];
+
+#[allow(dead_code)]
+pub fn detect_site(data: &str) -> Option<&'static dyn SiteParser> {
+    let mut head_end = data.len().min(2048);
+    while !data.is_char_boundary(head_end) { head_end -= 1; }
+    let head = &data[..head_end];
+    SITE_PARSERS.iter().copied().find(|site| site.detect(head))
+}
+
+pub struct PokerStars;
+
+impl SiteParser for PokerStars {
+    fn name(&self) -> &'static str { "PokerStars" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_pokerstars_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        let mut parser = Parser::new(data);
+        parser.parse();
+        parser.hands
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_pokerstars_hand_header(l)).count()
+    }
+}
+
+fn is_pokerstars_hand_header(line: &str) -> bool {
+    // PokerStars Hand #, PokerStars Zoom Hand #, PokerStars Home Game Hand #, PokerStars Game #
+    line.trim_start_matches('\u{feff}').starts_with("PokerStars ") && line.contains(" #")
+}
+
+// Paths are spelled out so the benches can include this file with #[path]
+#[path = "pklp/ggpoker.rs"]
+mod ggpoker;
+pub use ggpoker::GGPoker;
+
+#[path = "pklp/partypoker.rs"]
+mod partypoker;
+pub use partypoker::PartyPoker;
+
+#[path = "pklp/poker888.rs"]
+mod poker888;
+pub use poker888::Poker888;
+
+#[path = "pklp/winamax.rs"]
+mod winamax;
+pub use winamax::Winamax;
+
+#[path = "pklp/ipoker.rs"]
+mod ipoker;
+pub use ipoker::IPoker;
+
+#[path = "pklp/acr.rs"]
+mod acr;
+pub use acr::Acr;
+
+#[path = "pklp/phh.rs"]
+mod phh;
+#[allow(unused_imports)]
+pub use phh::{Phh, to_phh};
+
+#[path = "pklp/ohh.rs"]
+#[allow(dead_code)]
+mod ohh;
+#[allow(unused_imports)]
+pub use ohh::{Ohh, to_ohh_json};
+
+#[path = "pklp/acpc.rs"]
+mod acpc;
+#[allow(unused_imports)]
+pub use acpc::{Acpc, AcpcGame, acpc_state, parse_acpc, to_acpc};
+
+#[path = "pklp/render.rs"]
+mod render;
+#[allow(unused_imports)]
+pub use render::{render, render_hand};
+
+#[path = "pklp/state.rs"]
+mod state;
+#[allow(unused_imports)]
+pub use state::{HandState, PlayerState};
+
+#[path = "pklp/validate.rs"]
+mod validate;
+#[allow(unused_imports)]
+pub use validate::Violation;
+
+#[path = "pklp/results.rs"]
+mod results;
+#[allow(unused_imports)]
+pub use results::PlayerResult;
+
+#[path = "pklp/positions.rs"]
+mod positions;
+#[allow(unused_imports)]
+pub use positions::Position;
+
+#[path = "pklp/pots.rs"]
+mod pots;
+#[allow(unused_imports)]
+pub use pots::PotLayer;
+
+#[path = "pklp/eval.rs"]
+mod eval;
+#[allow(unused_imports)]
+pub use eval::{evaluate, evaluate_low, Category, Game, HandRank, LowRank, PotShowdown, Rank, Strength};
+
+#[path = "pklp/equity.rs"]
+mod equity;
+#[allow(unused_imports)]
+pub use equity::{equities, equity, AllIn};
+
+#[path = "pklp/range.rs"]
+mod range;
+#[allow(unused_imports)]
+pub use range::Range;
+
 enum ParseState {
     HandHeader,
     HoleCards,
@@ -319,6 +600,9 @@ struct Parser<'a> {
     header: Option<Header>,
     hole_cards: Option<HoleCards>,
     streets: Vec<Street>,
+    // Other sites using the PokerStars grammar set their name and actions, tried before the PokerStars actions
+    site_name: &'static str,
+    site_actions: Option<fn(&Parser) -> Option<Action>>,
 }
 
 impl<'a> Parser<'a> {
@@ -338,7 +622,9 @@ impl<'a> Parser<'a> {
             hands: Vec::new(),
             header: None, 
             hole_cards: None, 
-            streets: Vec::new()
+            streets: Vec::new(),
+            site_name: "PokerStars",
+            site_actions: None,
         };
         p.advance();
         p
@@ -352,7 +638,7 @@ impl<'a> Parser<'a> {
                 self.advance();
                 continue;
             }
-            else if line.starts_with("*** HOLE") {
+            else if line.starts_with("*** HOLE") || line.starts_with("*** DEALING") {
                 self.state = ParseState::HoleCards;
             }
             else if line.starts_with("*** SUMM") {
@@ -386,7 +672,12 @@ impl<'a> Parser<'a> {
                     let mut streets = Vec::new();
                     std::mem::swap(&mut self.streets, &mut streets);
 
-                    self.hands.push(Hand { header: header.unwrap(), hole_cards: hole_cards.unwrap(), streets, summary });
+                    // Stud hands deal every card on the streets and have no hole cards section
+                    let hole_cards = hole_cards.unwrap_or(HoleCards{dealt_to: Vec::new(), actions: ActionVec::new()});
+
+                    let mut hand = Hand { header: header.unwrap(), hole_cards, streets, summary };
+                    parse_summary_collected_hi_lo(&mut hand, self.data);
+                    self.hands.push(hand);
                 }
             }
         }
@@ -415,49 +706,70 @@ fn parse_header(p: &mut Parser) -> Header {
 fn parse_header_player(p: &mut Parser) -> Player {
     let line = p.next();
     let seat = str::parse::<u64>(&line["Seat ".len()..line.find(':').unwrap()]).unwrap() as u8;
-    let chips_begin = line.rfind('(').unwrap() + 1;
+    let chips_end = line.find(" in chips").unwrap();
+    let chips_begin = line[..chips_end].rfind('(').unwrap() + 1;
     let name_end = chips_begin - 2;
-    let chips_end = line.rfind(" in chips").unwrap();
     let chips = parse_currency(&line[chips_begin..chips_end]);
-    let bounty = if let Some(bounty_end) = line.rfind(" bounty") {
-        Some(parse_currency(&line[chips_end+2..bounty_end]))
-    } else {
-        None
-    };
+    let bounty = line.rfind(" bounty").map(|bounty_end| parse_currency(&line[chips_end + " in chips, ".len()..bounty_end]));
     Player{name: p.span(&line["Seat 0: ".len()..name_end]), seat, chips, bounty}
 }
 
 
 fn parse_header_table(p: &mut Parser) -> Table {
+    // Table 'Acamar V' 6-max Seat #3 is the button
+    // Table '123456789 1' 9-max Seat #1 is the button
+    // Table 'Aase III' 9-max (Play Money) Seat #5 is the button
     let line = p.next();
     let name = line.between('\'', '\'');
-    let seat_begin = line.rfind("#").unwrap() + 1;
-    let count_begin = name.len() + "Table '' ".len();
-    let max_players = parse_integer(&line[count_begin..]).unwrap().0 as u8;
-    let button = parse_integer(&line[seat_begin..]).unwrap().0 as u8;
-    Table { name: p.span(name), max_players, button }
+    let rest = line.get("Table '' ".len() + name.len()..).unwrap_or("").trim_start();
+    let max_players = parse_integer(rest).map_or(0, |(v, _)| v as u8);
+    let play_money = rest.contains("(Play Money)");
+    let button = line.rfind('#').and_then(|i| parse_integer(&line[i+1..])).map_or(0, |(v, _)| v as u8);
+    Table { name: p.span(name), max_players, button, play_money }
 }
 
 
 fn parse_header_info(p: &mut Parser) -> HandInfo {
+    // PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+    // PokerStars Zoom Hand #208966141595:  Hold'em No Limit ($0.05/$0.10) - 2020/02/08 4:01:19 ET
+    // PokerStars Home Game Hand #208966141595: {Club Name}  Hold'em No Limit ($0.01/$0.02) - 2020/02/08 4:01:19 ET
+    // PokerStars Hand #208966141595: Tournament #2804163510, $0.98+$0.12 USD Hold'em No Limit - Level I (10/20) - 2020/02/08 4:01:19 ET
+    // PokerStars Hand #208966141595:  HORSE (Razz Limit, $0.10/$0.20 USD) - 2020/02/08 4:01:19 ET
     let line = p.next();
     let id_begin = line.find('#').unwrap() + 1;
     let id_end = line.find(':').unwrap();
-    let buy_in_begin = line.find('(').unwrap() + 1;
-
     let id = &line[id_begin..id_end];
-    let game_type = line[id_end + 1..buy_in_begin-1].trim();
-    
-    let mut site_hand = line[0..id_begin - 1].trim().split(' ');
-    let (site, hand) = (site_hand.next().unwrap(), site_hand.next().unwrap());
-    
-    let buy_in_part = &line[buy_in_begin..line.find(')').unwrap()];
+
+    let prefix = line[0..id_begin - 1].trim();
+    let site = prefix.prefix(' ');
+    let hand = prefix.rsuffix(' ');
+    let is_zoom = prefix.contains(" Zoom ");
+
+    let mut rest = line[id_end + 1..].trim_start();
+    let home_game_club = if prefix.contains(" Home Game ") && rest.starts_with('{') {
+        let club = rest.between('{', '}');
+        rest = rest[club.len() + 2..].trim_start();
+        Some(p.span(club))
+    } else {
+        None
+    };
+
+    let buy_in_begin = rest.find('(').unwrap() + 1;
+    let mut game_type = rest[..buy_in_begin - 1].trim();
+    let mut mixed_game = None;
+
+    let mut buy_in_part = &rest[buy_in_begin..buy_in_begin + rest[buy_in_begin..].find(')').unwrap()];
+    if let Some(game_end) = buy_in_part.find(", ") {
+        mixed_game = Some(p.span(game_type));
+        game_type = &buy_in_part[..game_end];
+        buy_in_part = &buy_in_part[game_end + ", ".len()..];
+    }
     let buy_in_min_end = buy_in_part.find('/').unwrap();
     let buy_in_max_end = buy_in_min_end + buy_in_part[buy_in_min_end..].find(' ').unwrap_or(buy_in_part.len() - buy_in_min_end);
     let buy_in_min = parse_currency(&buy_in_part[..buy_in_min_end]);
     let buy_in_max = parse_currency(&buy_in_part[buy_in_min_end+1..buy_in_max_end]);
 
-    HandInfo{site: p.span(site), hand: p.span(hand), id: p.span(id), game_type: p.span(game_type), buy_in_min, buy_in_max}
+    HandInfo{site_name: p.site_name, site: p.span(site), hand: p.span(hand), id: p.span(id), is_zoom, home_game_club, mixed_game, game_type: p.span(game_type), buy_in_min, buy_in_max}
 }
 
 //endregion
@@ -468,22 +780,29 @@ fn parse_hole_cards(p: &mut Parser) -> HoleCards {
     p.advance();
 
     let mut dealt_to = Vec::new();
-    while p.line().starts_with("Dealt to") {
-        dealt_to.push(parse_hole_cards_dealt_to(p));
+    let mut actions = ActionVec::new();
+    loop {
+        while p.line().starts_with("Dealt to ") {
+            if let Some(d) = parse_hole_cards_dealt_to(p) {
+                dealt_to.push(d);
+            }
+        }
+        actions.append(&mut parse_action_list(p));
+        if !p.line().starts_with("Dealt to ") { break; }
     }
 
-    let actions = parse_action_list(p);
-
     HoleCards{dealt_to, actions}
 }
 
-fn parse_hole_cards_dealt_to(p: &mut Parser) -> (Span, Cards) {
+fn parse_hole_cards_dealt_to(p: &mut Parser) -> Option<(Span, Cards)> {
+    // Dealt to toyochan [Ac 6s]
+    // Dealt to toyochan             hidden cards of opponents
     let line = p.next();
     let name_cards = line.rsuffix_str("Dealt to ");
-    let cards_begin = name_cards.rfind('[').unwrap() + 1;
+    let cards_begin = name_cards.rfind('[')? + 1;
     let name = &name_cards[..cards_begin-2];
     let cards = parse_cards(&name_cards[cards_begin..name_cards.len()-1]);
-    (p.span(name), cards)
+    Some((p.span(name), cards))
 }
 
 //endregion
@@ -492,25 +811,106 @@ fn parse_hole_cards_dealt_to(p: &mut Parser) -> (Span, Cards) {
 
 fn parse_street(p: &mut Parser) -> Street {
     let header_line = p.next();
-    let actions = parse_action_list(p);
+
+    // Draw streets deal replacement cards in between the discards and the betting
+    let mut dealt_to = Vec::new();
+    let mut actions = parse_action_list(p);
+    while p.line().starts_with("Dealt to ") {
+        if let Some(d) = parse_street_dealt_to(p) {
+            dealt_to.push(d);
+        }
+        actions.append(&mut parse_action_list(p));
+    }
+
     let (index, t) = parse_street_type(header_line);
-    if let StreetType::Showdown = t {
-        Street{t, index, cards: None, new_card: None, actions}
+    match t {
+        StreetType::Showdown | StreetType::Draw => {
+            Street{t, index, cards: None, new_card: None, dealt_to, up_cards: Vec::new(), down_cards: Vec::new(), actions}
+        }
+        StreetType::Third | StreetType::Fourth | StreetType::Fifth | StreetType::Sixth | StreetType::Seventh => {
+            let (up_cards, down_cards) = parse_street_stud_cards(t, &dealt_to);
+            parse_street_stud_complete(&mut actions);
+            Street{t, index, cards: None, new_card: None, dealt_to, up_cards, down_cards, actions}
+        }
+        _ => {
+            let (cards, new_card) = parse_street_cards(header_line);
+            Street{t, index, cards: Some(cards), new_card, dealt_to, up_cards: Vec::new(), down_cards: Vec::new(), actions}
+        }
+    }
+}
+
+
+fn parse_street_dealt_to(p: &mut Parser) -> Option<(Span, Cards, Option<Cards>)> {
+    // Dealt to toyochan [7h 2c 8d 5s] [3d]
+    // Dealt to pondtree312             hidden cards of opponents
+    let line = p.next();
+    let name_cards = line.rsuffix_str("Dealt to ");
+    let cards_begin = name_cards.find(" [")?;
+    let name = &name_cards[..cards_begin];
+    let cards = parse_cards(name_cards.between('[', ']'));
+    let new_cards = if name_cards.matches('[').count() == 2 {
+        Some(parse_cards(name_cards.rbetween('[', ']')))
     } else {
-        let (cards, new_card) = parse_street_cards(header_line);
-        Street{t, index, cards: Some(cards), new_card, actions}
+        None
+    };
+    Some((p.span(name), cards, new_cards))
+}
+
+
+fn parse_street_stud_cards(t: StreetType, dealt_to: &[(Span, Cards, Option<Cards>)]) -> (PlayerCardsVec, PlayerCardsVec) {
+    // Dealt to toyochan [9s 4d 7c]                  3rd street, two down cards and one up card
+    // Dealt to pondtree312 [Kh]                     3rd street, only the up card of opponents is visible
+    // Dealt to toyochan [9s 4d 7c] [2h]             4th to 6th street, one up card
+    // Dealt to toyochan [9s 4d 7c 2h 5d Ah] [3s]    7th street, one down card
+    let mut up_cards = Vec::new();
+    let mut down_cards = Vec::new();
+    for (name, cards, new_cards) in dealt_to {
+        let new = new_cards.as_ref().unwrap_or(cards);
+        match t {
+            StreetType::Third if new.len() > 1 => {
+                down_cards.push((*name, new[..new.len()-1].iter().copied().collect()));
+                up_cards.push((*name, new[new.len()-1..].iter().copied().collect()));
+            }
+            StreetType::Seventh => down_cards.push((*name, new.clone())),
+            _ => up_cards.push((*name, new.clone())),
+        }
+    }
+    (up_cards, down_cards)
+}
+
+
+fn parse_street_stud_complete(actions: &mut ActionVec) {
+    // toyochan: completes it to $0.04 after a bring in of $0.02 raises by $0.02
+    let mut bring_in = None;
+    for a in actions.iter_mut() {
+        match a {
+            Action::Blind(BlindType::BringIn, _, amount) => bring_in = Some(amount.amount),
+            Action::Play(Play::Raise, _, Some(data)) if data.bet.amount == data.bet_to.unwrap().amount => {
+                data.bet.amount -= bring_in.unwrap_or(0.0);
+            }
+            _ => {}
+        }
     }
 }
 
 
-fn parse_street_type<'a>(line: &'a str) -> (u8, StreetType) {
+fn parse_street_type(line: &str) -> (u8, StreetType) {
     let end = line.rfind("***").unwrap();
-    let part = line[2..end].trim();
+    let part = line[3..end].trim();
     let t = match &part[part.len()-2..] {
         "OP" => StreetType::Flop,
         "RN" => StreetType::Turn,
         "ER" => StreetType::River,
+        "AW" => StreetType::Draw,
         "WN" => StreetType::Showdown,
+        // 3rd STREET ... 7th STREET
+        "ET" => match part.as_bytes()[0] {
+            b'3' => StreetType::Third,
+            b'4' => StreetType::Fourth,
+            b'5' => StreetType::Fifth,
+            b'6' => StreetType::Sixth,
+            _ => StreetType::Seventh,
+        },
         _ => unreachable!(),
     };
     let idx = if let Some((i, _)) = &["FIRST", "SECOND", "THIRD", "FOURTH"].iter().enumerate().find(|x| part.starts_with(*x.1)) {
@@ -522,7 +922,7 @@ fn parse_street_type<'a>(line: &'a str) -> (u8, StreetType) {
 }
 
 
-fn parse_street_cards<'a>(line: &'a str) -> (Cards, Option<Card>) {
+fn parse_street_cards(line: &str) -> (Cards, Option<Card>) {
     let cards = parse_cards(line.between('[', ']'));
     if line.matches('[').count() == 2 {
         (cards, Some(parse_cards(line.rbetween('[', ']'))[0]))
@@ -540,9 +940,14 @@ fn parse_summary(p: &mut Parser) -> Summary {
     p.advance();
     
     let mut boards = Vec::new();
-    let (pot, main_pot, side_pot, rake) = parse_summary_pot(p.next());
-
-    while p.line().starts_with("Hand was run twice") {
+    let mut seats = Vec::new();
+    let pot_line = p.next();
+    let (pot, main_pot, side_pots, rake) = parse_summary_pot(pot_line);
+    let side_pot = Currency{symbol: pot.symbol, amount: side_pots.iter().fold(0.0, |a, s| a + s.amount)};
+    let (jackpot, fees) = parse_summary_fees(p, pot_line);
+
+    // Hand was run twice, Hand was run two times
+    while p.line().starts_with("Hand was run ") {
         p.advance();
     }
 
@@ -551,16 +956,107 @@ fn parse_summary(p: &mut Parser) -> Summary {
         p.advance();
     }
 
+    let hi_lo_game = p.header.as_ref().is_some_and(|h| p.data[h.info.game_type].contains("Hi/Lo"));
     while p.line().starts_with("Seat ") {
-        p.advance();
+        seats.push(parse_summary_seat(p, hi_lo_game));
         if p.eof() { break; }
     }
 
-    Summary{pot, main_pot, side_pot, rake, boards}
+    Summary{pot, main_pot, side_pot, side_pots, rake, jackpot, fees, boards, seats}
 }
 
 
-fn parse_summary_pot<'a>(line: &'a str) -> (Currency, Currency, Currency, Option<Currency>) {
+fn parse_summary_fees(p: &Parser, line: &str) -> (Option<Currency>, Vec<(Span, Currency)>) {
+    // Total pot $2.32 | Rake $0.08 | Jackpot $0 | Bingo $0 | Fortune $0 | Tax $0
+    let mut jackpot = None;
+    let mut fees = Vec::new();
+    for part in line.split(" | ").skip(1).map(|x| x.trim()) {
+        let Some(amount_begin) = part.rfind(' ') else { continue };
+        let name = &part[..amount_begin];
+        if name == "Rake" { continue; }
+        if let Some((amount, _)) = parse_currency_dynamic(&part[amount_begin + 1..]) {
+            if name == "Jackpot" { jackpot = Some(amount); } else { fees.push((p.span(name), amount)); }
+        }
+    }
+    (jackpot, fees)
+}
+
+
+fn parse_summary_seat(p: &mut Parser, hi_lo_game: bool) -> SeatSummary {
+    // Seat 3: spr1teg (button) showed [8s Jd] and won ($2.85) with a pair of Eights
+    // Seat 1: pondtree312 collected ($1.25)
+    // Seat 2: Rifama (button) folded before Flop (didn't bet)
+    // Seat 4: Piscivore (big blind) mucked [Ah Kd]
+    // Seat 3: toyochan showed [Ah 2c 5d Kc] and won ($1) with HI: two pair, Aces and Kings, and won ($1) with LO: 5,4,3,2,A
+    // Seat 3: toyochan showed [Ah 2c 5d Kc] and won ($1) with a flush, and won ($1) with 8,6,5,3,2
+    let line = p.next();
+    let seat = parse_integer(&line["Seat ".len()..]).unwrap().0 as u8;
+    let rest = line.suffix_str(": ");
+    let name_end = [" (button)", " (small blind)", " (big blind)", " showed", " mucked", " folded", " collected (", " won ("]
+        .iter()
+        .filter_map(|k| rest.find(k))
+        .min()
+        .unwrap_or(rest.len());
+    let mucked = rest.contains(" mucked");
+    let cards = if rest.contains(" showed [") || rest.contains(" mucked [") { Some(parse_cards(rest.between('[', ']'))) } else { None };
+
+    let mut starts = rest.match_indices(" won (").chain(rest.match_indices(" collected (")).map(|(i, _)| i).collect::<Vec<_>>();
+    starts.sort_unstable();
+    let mut won = Vec::new();
+    for (i, begin) in starts.iter().enumerate() {
+        let part = &rest[*begin..starts.get(i + 1).copied().unwrap_or(rest.len())];
+        let amount = parse_currency(part.between('(', ')'));
+        let tail = part.suffix(')');
+        let hand = tail.strip_prefix(" with ").map(|h| h.trim_end_matches(", and").trim_end_matches(','));
+        let hi_lo = if hi_lo_game { parse_summary_hi_lo(tail) } else { None };
+        won.push(Award{amount, hi_lo, hand: hand.map(|h| p.span(h))});
+    }
+
+    SeatSummary{seat, name: p.span(&rest[..name_end]), cards, mucked, won}
+}
+
+
+// Only the labels written in the log, a scoop or an unlabelled award stays None
+fn parse_summary_hi_lo(tail: &str) -> Option<HiLo> {
+    let lower = tail.to_lowercase();
+    let (high, low) = (lower.contains("hi:") || lower.contains("(high)"), lower.contains("lo:") || lower.contains("(low)"));
+    if high == low {
+        None
+    }
+    else if low {
+        Some(HiLo::Low)
+    }
+    else {
+        Some(HiLo::High)
+    }
+}
+
+
+fn parse_summary_collected_hi_lo(hand: &mut Hand, data: &str) {
+    // "toyochan collected $1 from pot" does not say which half was collected, the summary does
+    let Hand{hole_cards, streets, summary, ..} = hand;
+    for seat in &summary.seats {
+        for award in seat.won.iter().filter(|a| a.hi_lo.is_some()) {
+            let collected = streets.iter_mut().rev()
+                .flat_map(|s| s.actions.iter_mut())
+                .chain(hole_cards.actions.iter_mut())
+                .find_map(|a| match a {
+                    Action::CollectedPot(_, name, amount, hi_lo @ None)
+                        if data[*name] == data[seat.name] && (amount.amount - award.amount.amount).abs() < 0.005 => Some(hi_lo),
+                    _ => None,
+                });
+            if let Some(hi_lo) = collected {
+                *hi_lo = award.hi_lo;
+            }
+        }
+    }
+}
+
+
+fn parse_summary_pot(line: &str) -> (Currency, Currency, Vec<Currency>, Option<Currency>) {
+    // Total pot $20 | Rake $0
+    // Total pot $20 Main pot $15. Side pot $5. | Rake $0
+    // Total pot $20 Main pot $10. Side pot-1 $6. Side pot-2 $4. | Rake $0
     let total_begin = line.find("Total pot ").unwrap();
     let total_pot = parse_currency_dynamic(&line[total_begin + "Total pot ".len()..]).unwrap().0;
     let main_pot = if let Some(begin) = line.find("Main pot ") {
@@ -568,23 +1064,16 @@ fn parse_summary_pot<'a>(line: &'a str) -> (Currency, Currency, Currency, Option
     } else {
         total_pot
     };
-    let side_pot = if let Some(begin) = line.rfind("Side pot ") {
-        parse_currency_dynamic(&line[begin + "Side pot ".len()..]).unwrap().0
-    } else {
-        Currency{symbol: total_pot.symbol, amount: 0.0}
-    };
-    let rake = if let Some(begin) = line.rfind("Rake ") {
-        let rake_part = &line[begin + "Rake ".len()..];
-        Some(if rake_part.contains(total_pot.symbol) {
-            parse_currency_dynamic(rake_part).unwrap().0
-        } else {
-            let amount = str::parse::<Float>(rake_part).unwrap();
-            Currency{symbol: total_pot.symbol, amount: amount}
-        })
-    } else {
-        None
-    };
-    (total_pot, main_pot, side_pot, rake)
+    let side_pots = line.match_indices("Side pot")
+        .filter_map(|(begin, _)| line[begin + "Side pot".len()..].split_once(' ').and_then(|(_, amount)| parse_currency_dynamic(amount)))
+        .map(|(c, _)| c)
+        .collect();
+    // "Rake 0" is written without a currency symbol
+    let rake = line.rfind("Rake ").map(|begin| {
+        let amount = parse_currency_dynamic(&line[begin + "Rake ".len()..]).unwrap().0.amount;
+        Currency{symbol: total_pot.symbol, amount}
+    });
+    (total_pot, main_pot, side_pots, rake)
 }
 
 //endregion
@@ -593,10 +1082,10 @@ fn parse_summary_pot<'a>(line: &'a str) -> (Currency, Currency, Currency, Option
 
 fn parse_action_list(p: &mut Parser) -> ActionVec {
     let mut actions = ActionVec::new();
-    while let Some(a) = parse_action(p) {
-        actions.push(a);
+    while !p.eof() && !p.line().is_empty() && p.line() != "\r" && !p.line().starts_with("***") && !p.line().starts_with("Dealt to ") {
+        let action = p.site_actions.and_then(|f| f(p)).or_else(|| parse_action(p));
+        actions.push(action.unwrap_or_else(|| Action::Unknown(p.span(p.line().trim_end()))));
         p.advance();
-        if p.eof() || p.line().starts_with("***") { break; }
     }
     actions
 }
@@ -608,7 +1097,10 @@ fn parse_action(p: &mut Parser) -> Option<Action> {
     // toyochan: checks
     // toyochan: folds
     // toyochan: discards
+    // toyochan: discards 2 cards
+    // toyochan: discards 2 cards [7h 2c]
     // toyochan: stands
+    // toyochan: stands pat
     // toyochan: doesn't show hand
     // toyochan: mucks hand
     // toyochan: sits out
@@ -630,6 +1122,9 @@ fn parse_action(p: &mut Parser) -> Option<Action> {
     // toyochan: posts small blind $0.25
     // toyochan: posts big blind $0.50
     // toyochan: posts small & big blinds $0.50
+    // toyochan: posts the ante $0.05
+    // toyochan: brings in for $0.02
+    // toyochan: completes it to $0.04
     // todochan: shows [Ac 6s] (a pair of Deuces)
 
     // toyochan said, "ANYTHING"
@@ -649,11 +1144,15 @@ fn parse_action(p: &mut Parser) -> Option<Action> {
     {
         Action::Fold(p.span(line.prefix(':')), None)
     }
-    else if line.ends_with(": discards")
+    else if line.contains(": discards")
     {
-        Action::Play(Play::Discard, p.span(line.prefix(':')), None)
+        let count_begin = line.find(": discards").unwrap() + ": discards ".len();
+        let count = line.get(count_begin..).and_then(parse_integer).map_or(0, |(v, _)| v as u8);
+        let cards = if line.ends_with(']') { Some(parse_cards(line.rbetween('[', ']'))) } else { None };
+        Action::Discard(p.span(line.prefix(':')), count, cards)
     }
-    else if line.ends_with(": stands")
+    else if line.ends_with(": stands") 
+         || line.ends_with(": stands pat")
     {
         Action::Play(Play::Stand, p.span(line.prefix(':')), None)
     }
@@ -712,16 +1211,29 @@ fn parse_action(p: &mut Parser) -> Option<Action> {
     else if let Some(a) = parse_bet_call_raise(p, ": raises ", Play::Raise) {
         a
     }
+    else if line.contains(": brings in for ")
+    {
+        Action::Blind(BlindType::BringIn, p.span(line.prefix(':')), parse_currency(line.rsuffix_str(": brings in for ")))
+    }
+    else if line.contains(": completes it to ")
+    {
+        let name_end = line.rfind(": completes it to ").unwrap();
+        let all_in = line.ends_with("and is all in") || line.ends_with("and is all-in");
+        let bet_end = if all_in { line.len() - " and is all in".len() } else { line.len() };
+        let bet = parse_currency(&line[name_end + ": completes it to ".len()..bet_end]);
+        Action::Play(Play::Raise, p.span(&line[..name_end]), Some(ActionData{bet, bet_to: Some(bet), all_in}))
+    }
     else if line.contains(": posts") 
     {
         let name_end = line.rfind(": posts ").unwrap();
         let blind_begin = name_end + ": posts ".len();
         let blind = parse_blind(&line[blind_begin..]).unwrap().0;
         let currency = parse_currency(&line[blind_begin + blind.len() + 1..]);
-        let bt = if blind.ends_with('s') {
-            BlindType::SmallAndBig
-        } else {
-            if blind.starts_with('s') { BlindType::Small } else { BlindType::Big }
+        let bt = match blind {
+            "small & big blinds" => BlindType::SmallAndBig,
+            "small blind" => BlindType::Small,
+            "the ante" => BlindType::Ante,
+            _ => BlindType::Big,
         };
         Action::Blind(bt, p.span(&line[0..name_end]), currency)
     }
@@ -738,15 +1250,10 @@ fn parse_action(p: &mut Parser) -> Option<Action> {
         let name_end = line.rfind(" collected ").unwrap();
         let currency_begin = name_end + " collected ".len();
         let currency_end = line.rfind(" from ").unwrap();
-        let pot_t_begin = currency_end + " from ".len();
         let name = &line[0..name_end];
-        let pot_t = match line[pot_t_begin..].chars().next().unwrap() {
-            'p' | 'm' => Pot::Main,
-            's' => Pot::Side,
-            _ => unreachable!()
-        };
+        let pot_t = parse_pot(&line[currency_end..]);
         let currency = parse_currency(&line[currency_begin..currency_end]);
-        Action::CollectedPot(pot_t, p.span(name), currency)
+        Action::CollectedPot(pot_t, p.span(name), currency, None)
     }
     else if line.contains(" cashed out the hand for ")
     {
@@ -824,16 +1331,14 @@ fn parse_integer(input: &str) -> Option<(u64, usize)> {
 
 
 fn extract_float(input: &str) -> Option<usize> {
-    if let Some(i) = extract_integer(input) {
-        Some(if input[i..].starts_with('.') {
-            extract_integer(&input[i+1..]).unwrap_or(i)
+    extract_integer(input).map(|i| {
+        if input[i..].starts_with('.') {
+            extract_integer(&input[i+1..]).map_or(i, |f| i + 1 + f)
         }
         else {
             i
-        })
-    } else {
-        None
-    }
+        }
+    })
 }
 
 
@@ -859,32 +1364,32 @@ fn parse_currency_char(input: &str) -> Option<(char, usize)> {
 
 
 fn parse_currency_dynamic(input: &str) -> Option<(Currency, usize)> {
-    if let Some((c, i)) = parse_currency_char(input) {
-        if let Some((v, end)) = parse_float(&input[i..]) {
-            return Some((Currency{symbol: c, amount: v}, end));
-        }
+    let (c, i) = parse_currency_char(input).unwrap_or((NO_CURRENCY, 0));
+    if let Some((v, end)) = parse_float(&input[i..]) {
+        return Some((Currency{symbol: c, amount: v}, i + end));
     }
     None
 }
 
 
-fn parse_currency<'a>(part: &'a str) -> Currency {
-    let (symbol, num_start) = parse_currency_char(part).unwrap();
+fn parse_currency(part: &str) -> Currency {
+    let (symbol, num_start) = parse_currency_char(part).unwrap_or((NO_CURRENCY, 0));
     if let Ok(v) = str::parse::<Float>(&part[num_start..]) {
-        Currency { symbol: symbol, amount: v }
+        Currency { symbol, amount: v }
     } 
     else if let Ok(v) = str::parse::<u64>(&part[num_start..]) {
-        Currency { symbol: symbol, amount: v as Float }
+        Currency { symbol, amount: v as Float }
     } 
     else {
-        panic!("Failed to parse float in string: '{}'", &part[1..]);
+        panic!("Failed to parse float in string: '{}'", &part[num_start..]);
     }
 }
 
 
-fn parse_cards<'a>(part: &'a str) -> Cards {
+fn parse_cards(part: &str) -> Cards {
     let mut cards = Cards::new();
-    for p in part.split(' ') {
+    // [8d 3c Ad], [ 8d, 3c, Ad ]
+    for p in part.split([' ', ',']).filter(|p| !p.is_empty()) {
         let mut chars = p.chars();
         let (nc, sc) = (chars.next().unwrap(), chars.next().unwrap());
         let n = match nc {
@@ -908,8 +1413,21 @@ fn parse_cards<'a>(part: &'a str) -> Cards {
 }
 
 
+fn parse_pot(part: &str) -> Pot {
+    // from pot, from main pot, from side pot, from side pot-2, from the side pot #1, from side pot 1
+    match part.find("side pot") {
+        Some(i) => {
+            let number = part[i + "side pot".len()..].trim_start_matches(['-', '#', ' ']);
+            let end = number.find(|c: char| !c.is_ascii_digit()).unwrap_or(number.len());
+            Pot::Side(number[..end].parse().ok())
+        }
+        None => Pot::Main,
+    }
+}
+
+
 fn parse_blind(input: &str) -> Option<(&str, usize)> {
-    for t in &["button", "small blind", "big blind", "button blind", "small & big blinds"] {
+    for t in &["button", "small blind", "big blind", "button blind", "small & big blinds", "the ante"] {
         if input.starts_with(t) {
             return Some((&input[0..t.len()], t.len()));
         }
@@ -917,6 +1435,329 @@ fn parse_blind(input: &str) -> Option<(&str, usize)> {
     None
 }
 
+
+#[allow(dead_code)]
+fn format_amount(v: Float) -> String {
+    // 100, 0.25
+    if v.fract() == 0.0 { format!("{}", v as i64) } else { format!("{}", v) }
+}
+
+
+fn parse_stakes(part: &str) -> Option<(Currency, Currency)> {
+    // $0.25/$0.50, 0.25€/0.50€
+    let (min, max) = part.split_once('/')?;
+    Some((parse_currency_any(min)?, parse_currency_any(max)?))
+}
+
+
+fn parse_currency_any(part: &str) -> Option<Currency> {
+    // $0.25, $0.25 USD, 0.25€, 25
+    let part = part.trim();
+    let (c, amount) = parse_currency_dynamic(part)?;
+    if c.symbol != NO_CURRENCY {
+        return Some(c);
+    }
+    let symbol = part[amount..].chars().next().and_then(|c| parse_currency_char(&part[amount..]).map(|_| c));
+    Some(Currency{symbol: symbol.unwrap_or(NO_CURRENCY), amount: c.amount})
+}
+
+
+fn parse_button(line: &str) -> Option<u8> {
+    // Seat 3 is the button, Table: 'Nice 05' 6-max (real money) Seat #3 is the button
+    let seat = line.strip_suffix(" is the button")?;
+    let seat = &seat[seat.rfind("Seat ")? + "Seat ".len()..];
+    parse_integer(seat.trim_start_matches('#')).map(|(v, _)| v as u8)
+}
+
+
+fn hand_blocks<'a>(data: &'a str, is_header: fn(&str) -> bool) -> Vec<Vec<&'a str>> {
+    let mut blocks: Vec<Vec<&'a str>> = Vec::new();
+    for line in data.lines() {
+        let line = line.trim_end();
+        if is_header(line) {
+            blocks.push(Vec::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            if !line.is_empty() { block.push(line); }
+        }
+    }
+    blocks
+}
+
+//endregion
+
+//region Parse - Builder
+
+// Collects the sections of a hand for sites that do not write the PokerStars sections 
+#[derive(Default)]
+struct HandBuilder<'a> {
+    header_actions: ActionVec,
+    hole_cards: Option<HoleCards>,
+    streets: Vec<Street>,
+    board: Cards,
+    // Sites that write the amount a player adds to the pot rather than the amount raised to
+    committed: HashMap<&'a str, Float>,
+    to_call: Float,
+    // Total pot written by the site, otherwise the blinds and bets are added up
+    total: Option<Currency>,
+    // Players read with seat(), they go to the header when the hand is finished
+    players: PlayerVec,
+}
+
+#[allow(dead_code)]
+impl<'a> HandBuilder<'a> {
+    fn actions(&mut self) -> &mut ActionVec {
+        if let Some(street) = self.streets.last_mut() {
+            &mut street.actions
+        } else if let Some(hole_cards) = self.hole_cards.as_mut() {
+            &mut hole_cards.actions
+        } else {
+            &mut self.header_actions
+        }
+    }
+
+    fn push(&mut self, action: Action) {
+        self.actions().push(action);
+    }
+
+    fn hole_cards(&mut self) -> &mut HoleCards {
+        self.hole_cards.get_or_insert_with(|| HoleCards{dealt_to: Vec::new(), actions: ActionVec::new()})
+    }
+
+    fn street(&mut self, t: StreetType, cards: Option<Cards>, new_card: Option<Card>) {
+        self.hole_cards();
+        self.committed.clear();
+        self.to_call = 0.0;
+        let index = self.streets.iter().filter(|s| std::mem::discriminant(&s.t) == std::mem::discriminant(&t)).count() as u8;
+        self.streets.push(Street{t, index, cards, new_card, dealt_to: Vec::new(), up_cards: Vec::new(), down_cards: Vec::new(), actions: ActionVec::new()});
+    }
+
+    fn deal_board(&mut self, t: StreetType, new: Cards) {
+        // Turn and river streets hold the previous board and the new card like PokerStars
+        if let StreetType::Flop = t {
+            self.street(t, Some(new.clone()), None);
+        } else {
+            self.street(t, Some(self.board.clone()), new.first().copied());
+        }
+        self.board.extend(new);
+    }
+
+    fn showdown(&mut self) {
+        if !matches!(self.streets.last(), Some(Street{t: StreetType::Showdown, ..})) {
+            self.street(StreetType::Showdown, None, None);
+        }
+    }
+
+    fn blind(&mut self, name: &'a str, span: Span, t: BlindType, amount: Currency) {
+        if !matches!(t, BlindType::Ante) {
+            let committed = self.committed.entry(name).or_insert(0.0);
+            *committed += amount.amount;
+            self.to_call = self.to_call.max(*committed);
+        }
+        self.push(Action::Blind(t, span, amount));
+    }
+
+    fn play(&mut self, name: &'a str, span: Span, added: Currency, all_in: bool) {
+        let committed = self.committed.entry(name).or_insert(0.0);
+        *committed += added.amount;
+        let total = *committed;
+        let (play, bet, bet_to) = if total <= self.to_call + 0.001 {
+            (Play::Call, added, None)
+        } else if self.to_call == 0.0 {
+            (Play::Bet, added, None)
+        } else {
+            let raise = Currency{symbol: added.symbol, amount: total - self.to_call};
+            (Play::Raise, raise, Some(Currency{symbol: added.symbol, amount: total}))
+        };
+        self.to_call = self.to_call.max(total);
+        self.push(Action::Play(play, span, Some(ActionData{bet, bet_to, all_in})));
+    }
+
+    fn raise_to(&mut self, name: &'a str, span: Span, to: Currency, all_in: bool) {
+        let committed = self.committed.get(name).copied().unwrap_or(0.0);
+        self.play(name, span, Currency{symbol: to.symbol, amount: to.amount - committed}, all_in);
+    }
+
+    fn seat(&mut self, line: &str, data: &str) -> bool {
+        // Seat 1: pondtree312 ($49.50), Seat 1: pondtree312 ( $49.50 USD ), Seat 2: Rifama (1500, 2€ bounty)
+        let player = || {
+            let seat = parse_integer(line.strip_prefix("Seat ")?)?.0 as u8;
+            let name = line[line.find(": ")? + 2..line.rfind('(')?].trim_end();
+            let mut stack = line.rbetween('(', ')').split(", ");
+            let chips = parse_currency_any(stack.next()?)?;
+            let bounty = stack.next().and_then(|b| parse_currency_any(b.trim_end_matches(" bounty")));
+            Some(Player{name: Span::from_str_slice(name, data), seat, chips, bounty})
+        };
+        player().map(|p| self.players.push(p)).is_some()
+    }
+
+    // Lines of some sites start with the player name without a separator, the longest seated name wins
+    fn player<'l>(&self, line: &'l str, data: &str) -> Option<(&'l str, &'l str)> {
+        self.players.iter()
+            .map(|p| &data[p.name])
+            .filter(|n| line.starts_with(n) && line[n.len()..].starts_with([' ', ':']))
+            .max_by_key(|n| n.len())
+            .map(|n| (&line[..n.len()], line[n.len()..].trim_start_matches(':').trim_start()))
+    }
+
+    fn dealt(&mut self, line: &str, data: &str) -> bool {
+        // Dealt to spr1teg [8s Jd], Dealt to spr1teg [ 8s, Jd ]
+        let Some(dealt) = line.strip_prefix("Dealt to ") else { return false };
+        let Some(name_end) = dealt.find(" [") else { return false };
+        let name = Span::from_str_slice(dealt[..name_end].trim(), data);
+        self.hole_cards().dealt_to.push((name, parse_cards(dealt.between('[', ']'))));
+        true
+    }
+
+    fn uncalled(&mut self, line: &str, data: &str) -> bool {
+        // Uncalled bet ($0.75) returned to spr1teg
+        let Some((amount, name)) = line.strip_prefix("Uncalled bet (").and_then(|l| l.split_once(") returned to ")) else { return false };
+        if let Some(amount) = parse_currency_any(amount) {
+            self.push(Action::UncalledBetReturned(Span::from_str_slice(name, data), amount));
+        }
+        true
+    }
+
+    // Actions worded the same by the sites, rest is the line after the player name
+    fn common_action(&mut self, span: Span, rest: &str) -> bool {
+        let action = if rest.starts_with("folds") {
+            Action::Fold(span, None)
+        }
+        else if rest.starts_with("checks") {
+            Action::Play(Play::Check, span, None)
+        }
+        else if rest.starts_with("shows ") {
+            self.showdown();
+            Action::ShowHand(span, parse_cards(rest.between('[', ']')))
+        }
+        else if rest.starts_with("mucks") {
+            self.showdown();
+            Action::Event(Event::NotShow, span)
+        }
+        else if rest.starts_with("doesn't show") || rest.starts_with("does not show") || rest.starts_with("did not show") {
+            // The last player left does not have to show, there is no showdown
+            Action::Event(Event::NotShow, span)
+        }
+        else if rest.starts_with("has joined the table") {
+            Action::Join(255, span)
+        }
+        else if rest.starts_with("has left the table") || rest.starts_with("leaves the table") {
+            Action::Event(Event::Leave, span)
+        }
+        else if rest.starts_with("is sitting out") || rest.starts_with("sits out") {
+            Action::Event(Event::Sitout, span)
+        }
+        else if rest.starts_with("is disconnected") || rest.starts_with("has been disconnected") {
+            Action::Event(Event::Disconnect, span)
+        }
+        else if rest.starts_with("is connected") || rest.starts_with("has been reconnected") {
+            Action::Event(Event::Connect, span)
+        }
+        else if rest.starts_with("did not respond in time") {
+            Action::Event(Event::Timeout, span)
+        }
+        else {
+            return false;
+        };
+        self.push(action);
+        true
+    }
+
+    fn finish(self, header: Header, rake: Option<Currency>, data: &str) -> Hand {
+        let HandBuilder{header_actions, hole_cards, streets, board, total, players, ..} = self;
+        let mut header = header;
+        header.actions = header_actions;
+        header.players.extend(players);
+        let hole_cards = hole_cards.unwrap_or(HoleCards{dealt_to: Vec::new(), actions: ActionVec::new()});
+        let mut hand = Hand{header, hole_cards, streets, summary: Summary{
+            pot: Currency{symbol: NO_CURRENCY, amount: 0.0},
+            main_pot: Currency{symbol: NO_CURRENCY, amount: 0.0},
+            side_pot: Currency{symbol: NO_CURRENCY, amount: 0.0},
+            side_pots: Vec::new(),
+            rake,
+            jackpot: None,
+            fees: Vec::new(),
+            boards: if board.is_empty() { Vec::new() } else { vec![board] },
+            seats: Vec::new(),
+        }};
+
+        // Sites without a summary section only write who showed and collected
+        let symbol = hand.header.info.buy_in_max.symbol;
+        let mut side_pots: Vec<Float> = Vec::new();
+        let mut seats: Vec<SeatSummary> = Vec::new();
+        for a in hand.actions() {
+            let (name, cards, award) = match a {
+                Action::ShowHand(name, cards) => (name, Some(cards), None),
+                Action::CollectedPot(pot, name, amount, hi_lo) => {
+                    if let Pot::Side(n) = pot {
+                        let i = n.unwrap_or(1).max(1) as usize - 1;
+                        if side_pots.len() <= i { side_pots.resize(i + 1, 0.0); }
+                        side_pots[i] += amount.amount;
+                    }
+                    (name, None, Some(Award{amount: *amount, hi_lo: *hi_lo, hand: None}))
+                }
+                _ => continue,
+            };
+            let Some(player) = hand.header.players.iter().find(|p| data[p.name] == data[*name]) else { continue };
+            let seat = match seats.iter_mut().find(|s| s.seat == player.seat) {
+                Some(seat) => seat,
+                None => {
+                    seats.push(SeatSummary{seat: player.seat, name: player.name, cards: None, mucked: false, won: Vec::new()});
+                    seats.last_mut().unwrap()
+                }
+            };
+            if let Some(cards) = cards { seat.cards = Some(cards.clone()); }
+            seat.won.extend(award);
+        }
+        seats.sort_by_key(|s| s.seat);
+
+        let pot = total.map_or_else(|| put_in(&hand, data), |t| t.amount);
+        let summary = &mut hand.summary;
+        let side_pot = side_pots.iter().fold(0.0, |a, s| a + s);
+        summary.pot = Currency{symbol, amount: pot};
+        summary.main_pot = Currency{symbol, amount: pot - side_pot};
+        summary.side_pot = Currency{symbol, amount: side_pot};
+        summary.side_pots = side_pots.into_iter().map(|amount| Currency{symbol, amount}).collect();
+        summary.seats = seats;
+        hand
+    }
+}
+
+// Blinds and bets less the uncalled bets, a bet nobody matched counts as returned even when the site does not say so
+fn put_in(hand: &Hand, data: &str) -> Float {
+    let mut invested: HashMap<&str, Float> = HashMap::new();
+    let preflop = hand.header.actions.iter().chain(hand.hole_cards.actions.iter()).collect::<Vec<_>>();
+    let rounds = std::iter::once(preflop).chain(hand.streets.iter().map(|s| s.actions.iter().collect()));
+    for actions in rounds {
+        let mut committed: HashMap<&str, Float> = HashMap::new();
+        for a in actions {
+            match a {
+                Action::Blind(BlindType::Ante, name, amount) => *invested.entry(&data[*name]).or_default() += amount.amount,
+                Action::Blind(_, name, amount) | Action::Play(Play::Call | Play::Bet, name, Some(ActionData{bet: amount, ..})) => {
+                    *committed.entry(&data[*name]).or_default() += amount.amount;
+                    *invested.entry(&data[*name]).or_default() += amount.amount;
+                }
+                Action::Play(Play::Raise, name, Some(d)) => {
+                    let max = committed.values().fold(0.0, |a: Float, b| a.max(*b));
+                    let to = d.bet_to.map_or(max + d.bet.amount, |to| to.amount);
+                    let committed = committed.entry(&data[*name]).or_default();
+                    *invested.entry(&data[*name]).or_default() += to - *committed;
+                    *committed = to;
+                }
+                Action::UncalledBetReturned(name, amount) => *invested.entry(&data[*name]).or_default() -= amount.amount,
+                _ => {}
+            }
+        }
+    }
+    let mut amounts = invested.into_values().collect::<Vec<_>>();
+    amounts.sort_by(|a, b| b.total_cmp(a));
+    let uncalled = match amounts.as_slice() {
+        [first, second, ..] => first - second,
+        _ => 0.0,
+    };
+    amounts.iter().sum::<Float>() - uncalled
+}
+
 //endregion
 
 //region JSON
@@ -928,8 +1769,19 @@ pub trait Json {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult;
 }
 
+// Sections computed from the hands, left out unless asked for
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonOptions {
+    pub results: bool,
+    pub positions: bool,
+}
+
 pub fn to_json<T: Json>(v: &T, data: &str) -> Result<String, std::fmt::Error> {
-    let mut f = JsonFormatter{out: String::with_capacity(100000), data};
+    to_json_with(v, data, JsonOptions::default())
+}
+
+pub fn to_json_with<T: Json>(v: &T, data: &str, options: JsonOptions) -> Result<String, std::fmt::Error> {
+    let mut f = JsonFormatter{out: String::with_capacity(100000), data, options};
     v.serialize(&mut f)?;
     Ok(f.out)
 }
@@ -937,6 +1789,7 @@ pub fn to_json<T: Json>(v: &T, data: &str) -> Result<String, std::fmt::Error> {
 pub struct JsonFormatter<'a> {
     out: String,
     data: &'a str,
+    options: JsonOptions,
 }
 
 pub struct JsonArrayFormatter<'a, 'b> {
@@ -1058,6 +1911,24 @@ impl Json for Span {
     }
 }
 
+// Text copied from the log that may contain quotes
+struct JsonText(Span);
+
+impl Json for JsonText {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        let data = f.data;
+        f.write_char('"')?;
+        for c in data[self.0].chars() {
+            match c {
+                '"' | '\\' => write!(f, "\\{}", c)?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
 impl<T: Json> Json for Option<T> {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         match self {
@@ -1097,7 +1968,7 @@ macro_rules! impl_stack_vec_serialize {
     };
 }
 
-impl_stack_vec_serialize!(5 8 20);
+impl_stack_vec_serialize!(5 7 8 10 20);
 
 
 
@@ -1111,28 +1982,48 @@ impl<T1: Json, T2: Json> Json for (T1, T2) {
     }
 }
 
+impl<T1: Json, T2: Json, T3: Json> Json for (T1, T2, T3) {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.write_char('[')?;
+        self.0.serialize(f)?;
+        f.write_char(',')?;
+        self.1.serialize(f)?;
+        f.write_char(',')?;
+        self.2.serialize(f)?;
+        f.write_char(']')
+    }
+}
+
 //endregion
 
 //region JSON - Structs
 
-const JSON_KEY_NAME   : &'static str = "name";
-const JSON_KEY_TYPE   : &'static str = "type";
-const JSON_KEY_AMOUNT : &'static str = "amount";
-const JSON_KEY_ACTIONS: &'static str = "actions";
+const JSON_KEY_NAME   : &str = "name";
+const JSON_KEY_TYPE   : &str = "type";
+const JSON_KEY_AMOUNT : &str = "amount";
+const JSON_KEY_ACTIONS: &str = "actions";
 
 
-impl<'a> Json for Hand {
+impl Json for Hand {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
-        f.object()
-            .entry("header", &self.header)
+        let results = f.options.results.then(|| self.results(f.data));
+        let positions = f.options.positions.then(|| self.positions(f.data));
+        let mut o = f.object();
+        o.entry("header", &self.header)
             .entry("preflop", &self.hole_cards)
             .entry("streets", &self.streets)
-            .entry("summary", &self.summary)
-            .finish()
+            .entry("summary", &self.summary);
+        if let Some(results) = &results {
+            o.entry("results", results);
+        }
+        if let Some(positions) = &positions {
+            o.entry("positions", positions);
+        }
+        o.finish()
     }
 }
 
-impl<'a> Json for Header {
+impl Json for Header {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
             .entry("info", &self.info)
@@ -1143,12 +2034,16 @@ impl<'a> Json for Header {
     }
 }
 
-impl<'a> Json for HandInfo {
+impl Json for HandInfo {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
+            .entry("site_name", &self.site_name)
             .entry("site", &self.site)
             .entry("hand", &self.hand)
             .entry("id", &self.id)
+            .entry("is_zoom", &self.is_zoom)
+            .entry("home_game_club", &self.home_game_club)
+            .entry("mixed_game", &self.mixed_game)
             .entry("game_type", &self.game_type)
             .entry("buy_in_min", &self.buy_in_min)
             .entry("buy_in_max", &self.buy_in_max)
@@ -1156,17 +2051,18 @@ impl<'a> Json for HandInfo {
     }
 }
 
-impl<'a> Json for Table {
+impl Json for Table {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
             .entry(JSON_KEY_NAME, &self.name)
             .entry("max_seats", &self.max_players)
             .entry("button", &self.button)
+            .entry("play_money", &self.play_money)
             .finish()
     }
 }
 
-impl<'a> Json for Player {
+impl Json for Player {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
             .entry(JSON_KEY_NAME, &self.name)
@@ -1177,7 +2073,7 @@ impl<'a> Json for Player {
     }
 }
 
-impl<'a> Json for HoleCards {
+impl Json for HoleCards {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
             .entry("dealt_to", &self.dealt_to)
@@ -1186,13 +2082,16 @@ impl<'a> Json for HoleCards {
     }
 }
 
-impl<'a> Json for Street {
+impl Json for Street {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.object()
             .entry(JSON_KEY_TYPE, &self.t)
             .entry("index", &self.index)
             .entry("cards", &self.cards)
             .entry("new_card", &self.new_card)
+            .entry("dealt_to", &self.dealt_to)
+            .entry("up_cards", &self.up_cards)
+            .entry("down_cards", &self.down_cards)
             .entry(JSON_KEY_ACTIONS, &self.actions)
             .finish()
     }
@@ -1204,8 +2103,34 @@ impl Json for Summary {
             .entry("pot", &self.pot)
             .entry("main_pot", &self.main_pot)
             .entry("side_pot", &self.side_pot)
+            .entry("side_pots", &self.side_pots)
             .entry("rake", &self.rake)
+            .entry("jackpot", &self.jackpot)
+            .entry("fees", &self.fees)
             .entry("boards", &self.boards)
+            .entry("seats", &self.seats)
+            .finish()
+    }
+}
+
+impl Json for SeatSummary {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("seat", &self.seat)
+            .entry(JSON_KEY_NAME, &self.name)
+            .entry("cards", &self.cards)
+            .entry("mucked", &self.mucked)
+            .entry("won", &self.won)
+            .finish()
+    }
+}
+
+impl Json for Award {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry(JSON_KEY_AMOUNT, &self.amount)
+            .entry("hi_lo", &self.hi_lo)
+            .entry("hand", &self.hand)
             .finish()
     }
 }
@@ -1216,6 +2141,12 @@ impl Json for StreetType {
             StreetType::Flop => "\"flop\"",
             StreetType::River => "\"river\"",
             StreetType::Turn => "\"turn\"",
+            StreetType::Draw => "\"draw\"",
+            StreetType::Third => "\"third_street\"",
+            StreetType::Fourth => "\"fourth_street\"",
+            StreetType::Fifth => "\"fifth_street\"",
+            StreetType::Sixth => "\"sixth_street\"",
+            StreetType::Seventh => "\"seventh_street\"",
             StreetType::Showdown => "\"showdown\"",
         })
     }
@@ -1227,6 +2158,8 @@ impl Json for BlindType {
             BlindType::Small => "\"small\"",
             BlindType::Big => "\"big\"",
             BlindType::SmallAndBig => "\"both\"",
+            BlindType::Ante => "\"ante\"",
+            BlindType::BringIn => "\"bring_in\"",
         })
     }
 }
@@ -1244,15 +2177,25 @@ impl Json for Suit {
     }
 }
 
-impl Json for Pot {
+impl Json for HiLo {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         f.write_str(match self {
-            Pot::Main => "\"main\"",
-            Pot::Side => "\"side\""
+            HiLo::High => "\"high\"",
+            HiLo::Low => "\"low\""
         })
     }
 }
 
+impl Json for Pot {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        match self {
+            Pot::Main => f.write_str("\"main\""),
+            Pot::Side(None) => f.write_str("\"side\""),
+            Pot::Side(Some(n)) => write!(f, "\"side-{}\"", n),
+        }
+    }
+}
+
 impl Json for Action {
     fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
         let mut o = f.object();
@@ -1265,6 +2208,7 @@ impl Json for Action {
                     Event::Leave => "leave",
                     Event::Sitout => "sitout",
                     Event::NotShow => "not_show",
+                    Event::EvCashOut => "ev_cash_out",
                 });
                 o.entry(JSON_KEY_NAME, name);
             }
@@ -1280,6 +2224,7 @@ impl Json for Action {
                     Play::Check => "check",
                     Play::Raise => "raise",
                     Play::Stand => "stand",
+                    #[allow(deprecated)]
                     Play::Discard => "discard",
                 });
                 o.entry(JSON_KEY_NAME, name);
@@ -1306,6 +2251,12 @@ impl Json for Action {
                 o.entry(JSON_KEY_NAME, name);
                 o.entry("cards", cards);
             }
+            Action::Discard(name, count, cards) => {
+                o.entry(JSON_KEY_TYPE, &"discard");
+                o.entry(JSON_KEY_NAME, name);
+                o.entry("count", count);
+                o.entry("cards", cards);
+            }
             Action::Say(name, _msg) => {
                 o.entry(JSON_KEY_TYPE, &"say");
                 o.entry(JSON_KEY_NAME, name);
@@ -1319,16 +2270,26 @@ impl Json for Action {
                 o.entry("fee", fee);
 
             }
+            Action::PaysCashOutRisk(name, amount) => {
+                o.entry(JSON_KEY_TYPE, &"pay_cash_out_risk");
+                o.entry(JSON_KEY_NAME, name);
+                o.entry(JSON_KEY_AMOUNT, amount);
+            }
             Action::UncalledBetReturned(name, amount) => {
                 o.entry(JSON_KEY_TYPE, &"return_bet");
                 o.entry(JSON_KEY_NAME, name);
                 o.entry(JSON_KEY_AMOUNT, amount);
             }
-            Action::CollectedPot(pot, name, amount) => {
+            Action::CollectedPot(pot, name, amount, hi_lo) => {
                 o.entry(JSON_KEY_TYPE, &"collect_pot");
                 o.entry(JSON_KEY_NAME, name);
                 o.entry(JSON_KEY_AMOUNT, amount);
                 o.entry("pot", pot);
+                o.entry("hi_lo", hi_lo);
+            }
+            Action::Unknown(line) => {
+                o.entry(JSON_KEY_TYPE, &"unknown");
+                o.entry("line", &JsonText(*line));
             }
         }
         o.finish()
@@ -1354,4 +2315,495 @@ impl Json for Card {
 mod tests {
     use super::*;
 
+    #[test]
+    fn header_variants() {
+        let data = "PokerStars Zoom Hand #208966141595:  Hold'em No Limit ($0.05/$0.10) - 2020/02/08 4:01:19 ET
+Table 'Donati' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($10 in chips)
+Seat 2: Rifama ($10 in chips)
+pondtree312: posts small blind $0.05
+Rifama: posts big blind $0.10
+*** HOLE CARDS ***
+pondtree312: folds
+Uncalled bet ($0.05) returned to Rifama
+Rifama collected $0.10 from pot
+*** SUMMARY ***
+Total pot $0.10 | Rake $0
+
+
+PokerStars Home Game Hand #208966141596: {Club Name}  Hold'em No Limit (10/20) - 2020/02/08 4:01:19 ET
+Table 'Club Table' 9-max (Play Money) Seat #2 is the button
+Seat 1: pondtree312 (1500 in chips)
+Seat 2: Rifama (1500 in chips) is sitting out
+Seat 3: spr1teg (1500 in chips, $5 bounty)
+pondtree312: posts the ante 5
+spr1teg: posts the ante 5
+pondtree312: posts small blind 10
+spr1teg: posts big blind 20
+*** HOLE CARDS ***
+pondtree312: folds
+Uncalled bet (10) returned to spr1teg
+spr1teg collected 30 from pot
+spr1teg finished the tournament in 2nd place
+*** SUMMARY ***
+Total pot 30 | Rake 0
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 2);
+
+        let zoom = &hands[0].header;
+        assert!(zoom.info.is_zoom);
+        assert!(zoom.info.home_game_club.is_none());
+        assert_eq!(&data[zoom.info.site], "PokerStars");
+        assert_eq!(&data[zoom.info.game_type], "Hold'em No Limit");
+        assert!(!zoom.table.play_money);
+        assert_eq!(hands[0].summary.pot.amount, 0.10);
+
+        let home = &hands[1].header;
+        assert!(!home.info.is_zoom);
+        assert_eq!(&data[home.info.home_game_club.unwrap()], "Club Name");
+        assert_eq!(&data[home.info.game_type], "Hold'em No Limit");
+        assert_eq!(home.info.buy_in_max.symbol, NO_CURRENCY);
+        assert_eq!(home.info.buy_in_max.amount, 20.0);
+        assert!(home.table.play_money);
+        assert_eq!(home.table.max_players, 9);
+        assert_eq!(home.table.button, 2);
+        assert_eq!(home.players.len(), 3);
+        assert_eq!(&data[home.players[1].name], "Rifama");
+        assert_eq!(home.players[2].bounty.unwrap().amount, 5.0);
+        assert!(matches!(home.actions[0], Action::Blind(BlindType::Ante, _, _)));
+        assert_eq!(hands[1].hole_cards.actions.len(), 4);
+        assert!(matches!(hands[1].hole_cards.actions[3], Action::Unknown(line) if &data[line] == "spr1teg finished the tournament in 2nd place"));
+        assert!(to_json(&hands, data).unwrap().contains(r#"{"type":"unknown","line":"spr1teg finished the tournament in 2nd place"}"#));
+    }
+
+    #[test]
+    fn float_amounts() {
+        // The length covers the whole number, fraction included
+        assert_eq!(parse_float("12.50 in chips"), Some((12.5, 5)));
+        assert_eq!(parse_float("0.05)"), Some((0.05, 4)));
+        assert_eq!(parse_float("1500 "), Some((1500.0, 4)));
+        assert_eq!(parse_float("3. "), Some((3.0, 1)));
+        assert_eq!(parse_float("x"), None);
+        assert_eq!(parse_currency_dynamic("$1.25 to").map(|(c, i)| (c.amount, i)), Some((1.25, 5)));
+    }
+
+    #[test]
+    fn draw_streets() {
+        let data = "PokerStars Hand #208966141597:  Triple Draw 2-7 Lowball Limit ($0.10/$0.20 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($4 in chips)
+Seat 2: Rifama ($4 in chips)
+Rifama: posts small blind $0.05
+pondtree312: posts big blind $0.10
+*** DEALING HANDS ***
+Dealt to Rifama [7h 2c 8d 5s Kc]
+Rifama: calls $0.05
+pondtree312: checks
+*** FIRST DRAW ***
+Rifama: discards 1 card [Kc]
+Dealt to Rifama [7h 2c 8d 5s] [3d]
+pondtree312: discards 2 cards
+Rifama: bets $0.10
+pondtree312: calls $0.10
+*** SECOND DRAW ***
+Rifama: stands pat
+pondtree312: stands pat
+Rifama: checks
+pondtree312: checks
+*** SHOW DOWN ***
+Rifama: shows [7h 2c 8d 5s 3d] (Lo: 8,7,5,3,2)
+Rifama collected $0.38 from pot
+*** SUMMARY ***
+Total pot $0.40 | Rake $0.02
+Seat 1: pondtree312 (button) showed [9h 8c 6d 4s 2h] and lost with Lo: 9,8,6,4,2
+Seat 2: Rifama (small blind) showed [7h 2c 8d 5s 3d] and won ($0.38) with Lo: 8,7,5,3,2
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(hand.hole_cards.dealt_to[0].1.len(), 5);
+        assert_eq!(&data[hand.hero().unwrap()], "Rifama");
+        assert_eq!(format!("{:?}", hand.known_hole_cards(data)["Rifama"]), "[7h, 2c, 8d, 5s, 3d]");
+        assert_eq!(hand.streets.len(), 3);
+
+        let first = &hand.streets[0];
+        assert!(matches!(first.t, StreetType::Draw));
+        assert_eq!(first.index, 0);
+        assert!(first.cards.is_none());
+        assert_eq!(first.dealt_to.len(), 1);
+        assert_eq!(&data[first.dealt_to[0].0], "Rifama");
+        assert_eq!(first.dealt_to[0].1.len(), 4);
+        assert_eq!(first.dealt_to[0].2.as_ref().unwrap().len(), 1);
+        assert_eq!(first.actions.len(), 4);
+        assert!(matches!(first.actions[0], Action::Discard(_, 1, Some(_))));
+        assert!(matches!(first.actions[1], Action::Discard(_, 2, None)));
+
+        let second = &hand.streets[1];
+        assert!(matches!(second.t, StreetType::Draw));
+        assert_eq!(second.index, 1);
+        assert!(matches!(second.actions[0], Action::Play(Play::Stand, _, None)));
+    }
+
+    #[test]
+    fn draw_hidden_dealt_to() {
+        let data = "PokerStars Hand #208966141597:  Triple Draw 2-7 Lowball Limit ($0.10/$0.20 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($4 in chips)
+Seat 2: Rifama ($4 in chips)
+Rifama: posts small blind $0.05
+pondtree312: posts big blind $0.10
+*** DEALING HANDS ***
+Dealt to Rifama [7h 2c 8d 5s Kc]
+Rifama: calls $0.05
+pondtree312: checks
+*** FIRST DRAW ***
+Rifama: discards 1 card [Kc]
+Dealt to Rifama [7h 2c 8d 5s] [3d]
+pondtree312: discards 2 cards
+Dealt to pondtree312
+Rifama: bets $0.10
+pondtree312: folds
+Uncalled bet ($0.10) returned to Rifama
+Rifama collected $0.19 from pot
+*** SUMMARY ***
+Total pot $0.20 | Rake $0.01
+Seat 2: Rifama (small blind) collected ($0.19)
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let first = &hands[0].streets[0];
+        assert_eq!(first.dealt_to.len(), 1);
+        assert_eq!(&data[first.dealt_to[0].0], "Rifama");
+        assert_eq!(first.actions.len(), 6);
+        assert!(matches!(first.actions[1], Action::Discard(_, 2, None)));
+        assert!(matches!(first.actions[3], Action::Fold(..)));
+    }
+
+    #[test]
+    fn stud_streets() {
+        let data = "PokerStars Hand #208966141598:  HORSE (Razz Limit, $0.04/$0.08 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 8-max
+Seat 1: pondtree312 ($2 in chips)
+Seat 2: Rifama ($2 in chips)
+pondtree312: posts the ante $0.01
+Rifama: posts the ante $0.01
+*** 3rd STREET ***
+Dealt to pondtree312 [Kh]
+Dealt to Rifama [9s 4d 2c]
+pondtree312: brings in for $0.02
+Rifama: completes it to $0.04
+pondtree312: calls $0.02
+*** 4th STREET ***
+Dealt to pondtree312 [Kh] [5c]
+Dealt to Rifama [9s 4d 2c] [7h]
+Rifama: bets $0.04
+pondtree312: calls $0.04
+*** 7th STREET ***
+Dealt to Rifama [9s 4d 2c 7h 5d Ah] [3s]
+Rifama: bets $0.08
+pondtree312: folds
+Uncalled bet ($0.08) returned to Rifama
+Rifama collected $0.18 from pot
+*** SUMMARY ***
+Total pot $0.18 | Rake $0
+Seat 2: Rifama collected ($0.18)
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.info.mixed_game.unwrap()], "HORSE");
+        assert_eq!(&data[hand.header.info.game_type], "Razz Limit");
+        assert_eq!(hand.header.info.buy_in_max.amount, 0.08);
+        assert_eq!(hand.header.table.button, 0);
+        assert!(hand.hole_cards.dealt_to.is_empty());
+        assert_eq!(hand.streets.len(), 3);
+
+        let third = &hand.streets[0];
+        assert!(matches!(third.t, StreetType::Third));
+        assert!(third.cards.is_none());
+        assert_eq!(third.up_cards.len(), 2);
+        assert_eq!(third.down_cards.len(), 1);
+        assert_eq!(&data[third.down_cards[0].0], "Rifama");
+        assert_eq!(third.down_cards[0].1.len(), 2);
+        assert!(matches!(third.actions[0], Action::Blind(BlindType::BringIn, _, _)));
+        match &third.actions[1] {
+            Action::Play(Play::Raise, _, Some(data)) => assert_eq!(data.bet.amount, 0.02),
+            a => panic!("Unexpected action {:?}", a),
+        }
+
+        let fourth = &hand.streets[1];
+        assert!(matches!(fourth.t, StreetType::Fourth));
+        assert_eq!(fourth.up_cards.len(), 2);
+        assert!(fourth.down_cards.is_empty());
+
+        let seventh = &hand.streets[2];
+        assert!(matches!(seventh.t, StreetType::Seventh));
+        assert!(seventh.up_cards.is_empty());
+        assert_eq!(seventh.down_cards[0].1.len(), 1);
+        assert_eq!(seventh.dealt_to[0].1.len(), 6);
+
+        assert_eq!(&data[hand.hero().unwrap()], "Rifama");
+        let cards = hand.known_hole_cards(data);
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards["Rifama"].len(), 7);
+    }
+
+    #[test]
+    fn hi_lo_awards() {
+        let data = "PokerStars Hand #208966141599:  Omaha Hi/Lo Pot Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+Rifama: posts small blind $0.25
+spr1teg: posts big blind $0.50
+*** HOLE CARDS ***
+pondtree312: calls $0.50
+Rifama: calls $0.25
+spr1teg: checks
+*** FLOP *** [8d 3c Ad]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** TURN *** [8d 3c Ad] [9c]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** RIVER *** [8d 3c Ad 9c] [6d]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** SHOW DOWN ***
+Rifama: shows [Ah 2c 5d Kc] (HI: a pair of Aces; LO: 6,5,3,2,A)
+spr1teg: shows [9h 9d Qs Js] (HI: three of a kind, Nines)
+pondtree312: mucks hand
+spr1teg collected $0.72 from pot
+Rifama collected $0.71 from pot
+*** SUMMARY ***
+Total pot $1.50 | Rake $0.07
+Board [8d 3c Ad 9c 6d]
+Seat 1: pondtree312 (button) mucked [Kh Kd Qh Jc]
+Seat 2: Rifama (small blind) showed [Ah 2c 5d Kc] and won ($0.71) with LO: 6,5,3,2,A
+Seat 3: spr1teg (big blind) showed [9h 9d Qs Js] and won ($0.72) with HI: three of a kind, Nines
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let summary = &hands[0].summary;
+        assert_eq!(summary.seats.len(), 3);
+        assert!(summary.seats[0].mucked);
+        assert_eq!(summary.seats[0].cards.as_ref().unwrap().len(), 4);
+        assert_eq!(summary.seats[1].won[0].hi_lo, Some(HiLo::Low));
+        assert_eq!(&data[summary.seats[1].won[0].hand.unwrap()], "LO: 6,5,3,2,A");
+        assert_eq!(summary.seats[2].won[0].hi_lo, Some(HiLo::High));
+
+        let showdown = &hands[0].streets[3].actions;
+        assert!(matches!(showdown[3], Action::CollectedPot(Pot::Main, _, _, Some(HiLo::High))));
+        assert!(matches!(showdown[4], Action::CollectedPot(Pot::Main, _, _, Some(HiLo::Low))));
+
+        let cards = hands[0].known_hole_cards(data);
+        assert_eq!(cards.len(), 3);
+        assert_eq!(format!("{:?}", cards["pondtree312"]), "[13h, 13d, 12h, 11c]");
+        assert_eq!(format!("{:?}", hands[0].known_hole_cards_of_seat(3, data).unwrap()), "[9h, 9d, 12s, 11s]");
+        assert!(hands[0].known_hole_cards_of_seat(4, data).is_none());
+        assert!(hands[0].hero().is_none());
+
+        assert_eq!(parse_summary_hi_lo(" with a flush, and"), None);
+        assert_eq!(parse_summary_hi_lo(" with 8,6,5,3,2"), None);
+        assert_eq!(parse_summary_hi_lo(" with LO: 8,6,5,3,2"), Some(HiLo::Low));
+    }
+    #[test]
+    fn hi_lo_scoop_and_side_pot() {
+        let data = "PokerStars Hand #208966141600:  Omaha Hi/Lo Pot Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+Rifama: posts small blind $0.25
+spr1teg: posts big blind $0.50
+*** HOLE CARDS ***
+pondtree312: calls $0.50
+Rifama: calls $0.25
+spr1teg: checks
+*** FLOP *** [8d 3c Kd]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** TURN *** [8d 3c Kd] [9c]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** RIVER *** [8d 3c Kd 9c] [Qd]
+Rifama: checks
+spr1teg: checks
+pondtree312: checks
+*** SHOW DOWN ***
+spr1teg: shows [9h 9d Qs Js] (three of a kind, Nines)
+pondtree312: mucks hand
+Rifama: mucks hand
+spr1teg collected $1.50 from pot
+*** SUMMARY ***
+Total pot $1.50 | Rake $0
+Board [8d 3c Kd 9c Qd]
+Seat 1: pondtree312 (button) mucked [Kh Kc Qh Jc]
+Seat 2: Rifama (small blind) mucked [Ah 2c 5d Tc]
+Seat 3: spr1teg (big blind) showed [9h 9d Qs Js] and won ($1.50) with three of a kind, Nines
+
+
+PokerStars Hand #208966141601:  Omaha Hi/Lo Pot Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($1 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+Rifama: posts small blind $0.25
+spr1teg: posts big blind $0.50
+*** HOLE CARDS ***
+pondtree312: raises $0.50 to $1 and is all-in
+Rifama: calls $0.75
+spr1teg: calls $0.50
+*** FLOP *** [8d 3c Ad]
+Rifama: bets $2
+spr1teg: calls $2
+*** TURN *** [8d 3c Ad] [9c]
+Rifama: checks
+spr1teg: checks
+*** RIVER *** [8d 3c Ad 9c] [6d]
+Rifama: checks
+spr1teg: checks
+*** SHOW DOWN ***
+spr1teg: shows [9h 9d Qs Js] (HI: three of a kind, Nines)
+Rifama: mucks hand
+spr1teg collected $4 from side pot
+pondtree312: shows [2h 4s Kc Kd] (HI: a pair of Aces; LO: 6,4,3,2,A)
+spr1teg collected $1.50 from main pot
+pondtree312 collected $1.50 from main pot
+*** SUMMARY ***
+Total pot $7 Main pot $3. Side pot $4. | Rake $0
+Board [8d 3c Ad 9c 6d]
+Seat 1: pondtree312 (button) showed [2h 4s Kc Kd] and won ($1.50) with LO: 6,4,3,2,A
+Seat 2: Rifama (small blind) mucked [Kh Qd Th Jc]
+Seat 3: spr1teg (big blind) showed [9h 9d Qs Js] and won ($4) with three of a kind, Nines, and won ($1.50) with HI: three of a kind, Nines
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 2);
+
+        // No low qualified, the one award is the whole pot
+        let scoop = &hands[0];
+        assert_eq!(scoop.summary.seats[2].won.len(), 1);
+        assert_eq!(scoop.summary.seats[2].won[0].hi_lo, None);
+        assert!(matches!(scoop.streets[3].actions[3], Action::CollectedPot(Pot::Main, _, _, None)));
+
+        // The side pot is scooped, only the main pot is split
+        let side = &hands[1];
+        let won = &side.summary.seats[2].won;
+        assert_eq!((won[0].amount.amount, won[0].hi_lo), (4.0, None));
+        assert_eq!((won[1].amount.amount, won[1].hi_lo), (1.5, Some(HiLo::High)));
+        assert_eq!(side.summary.seats[0].won[0].hi_lo, Some(HiLo::Low));
+        let collected = side.streets[3].actions.iter()
+            .filter_map(|a| match a { Action::CollectedPot(pot, name, _, hi_lo) => Some((*pot, &data[*name], *hi_lo)), _ => None })
+            .map(|(pot, name, hi_lo)| (matches!(pot, Pot::Main), name, hi_lo))
+            .collect::<Vec<_>>();
+        assert_eq!(collected, [(false, "spr1teg", None), (true, "spr1teg", Some(HiLo::High)), (true, "pondtree312", Some(HiLo::Low))]);
+
+        assert_eq!(parse_summary_hi_lo(" with HI: a straight, Five high and LO: 5,4,3,2,A"), None);
+    }
+    #[test]
+    fn known_hole_cards_per_seat() {
+        let data = "PokerStars Hand #208966141602:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #5 is the button
+Seat 1: pondtree312 ($50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+Seat 4: Piscivore ($50 in chips)
+Seat 5: toyochan ($50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+Dealt to Rifama [Qh Qd]
+spr1teg: raises $1 to $1.50
+Piscivore: calls $1.50
+toyochan: folds
+pondtree312: folds [7h 2c]
+Rifama: calls $1
+*** FLOP *** [8d 3c Ad]
+Rifama: checks
+spr1teg: checks
+Piscivore: checks
+*** TURN *** [8d 3c Ad] [9c]
+Rifama: checks
+spr1teg: checks
+Piscivore: checks
+*** RIVER *** [8d 3c Ad 9c] [6d]
+Rifama: checks
+spr1teg: checks
+Piscivore: checks
+*** SHOW DOWN ***
+Rifama: shows [Qh Qd] (a pair of Queens)
+spr1teg: shows [Ts 9s] (a pair of Nines)
+Piscivore: mucks hand
+Rifama collected $4.75 from pot
+*** SUMMARY ***
+Total pot $4.75 | Rake $0
+Board [8d 3c Ad 9c 6d]
+Seat 1: pondtree312 (small blind) folded before Flop
+Seat 2: Rifama (big blind) showed [Qh Qd] and won ($4.75) with a pair of Queens
+Seat 3: spr1teg showed [Ts 9s] and lost with a pair of Nines
+Seat 4: Piscivore mucked [Kh Jd]
+Seat 5: toyochan (button) folded before Flop (didn't bet)
+";
+        let hands = parse_string(data);
+        let hand = &hands[0];
+        let seat = |seat: u8| hand.known_hole_cards_of_seat(seat, data).map(|c| c.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "));
+        assert_eq!(&data[hand.hero().unwrap()], "Rifama");
+        assert_eq!(seat(2).as_deref(), Some("Qh Qd"));
+        assert_eq!(seat(3).as_deref(), Some("Ts 9s"));
+        assert_eq!(seat(1).as_deref(), Some("7h 2c"));
+        assert_eq!(seat(4).as_deref(), Some("Kh Jd"));
+        assert_eq!(seat(5), None);
+        assert_eq!(seat(6), None);
+        assert_eq!(hand.known_hole_cards(data).len(), 4);
+    }
+    #[test]
+    fn site_detection() {
+        let site = |data: &str| detect_site(data).map(|s| s.name());
+        let stars = "PokerStars Hand #208966141595:  Hold'em No Limit ($0.05/$0.10) - 2020/02/08 4:01:19 ET
+Table 'Donati' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($10 in chips)
+Seat 2: Rifama ($10 in chips)
+pondtree312: posts small blind $0.05
+Rifama: posts big blind $0.10
+*** HOLE CARDS ***
+pondtree312: folds
+Uncalled bet ($0.05) returned to Rifama
+Rifama collected $0.10 from pot
+*** SUMMARY ***
+Total pot $0.10 | Rake $0
+";
+        assert_eq!(site(stars), Some("PokerStars"));
+        assert_eq!(site(&format!("\u{feff}{}", stars)), Some("PokerStars"));
+        assert_eq!(site(&format!("{}\n\n\n{}", stars.replace("208966141595", "208966141596"), stars)), Some("PokerStars"));
+        assert_eq!(PokerStars.count_hands(&format!("{}\n\n\n{}", stars, stars)), 2);
+
+        assert_eq!(site("Poker Hand #RC1234567: Hold'em No Limit ($0.02/$0.05) - 2023/01/01 12:00:00"), Some("GGPoker"));
+        assert_eq!(site("***** Hand History for Game 12345 *****"), Some("PartyPoker"));
+        assert_eq!(site("#Game No : 12345\n***** 888poker Hand History for Game 12345 *****"), Some("888poker"));
+        assert_eq!(site("Winamax Poker - CashGame - HandId: #1-2-3 - Holdem no limit (0.01€/0.02€)"), Some("Winamax"));
+        assert_eq!(site("<session sessioncode=\"1\">\n<general></general>\n<game gamecode=\"2\">"), Some("iPoker"));
+        assert_eq!(site("Game Hand #12345 - Holdem(No Limit) - $0.01/$0.02 - 2023/01/01 12:00:00 UTC"), Some("ACR"));
+        assert_eq!(site("variant = 'NT'\nantes = [0, 0]"), Some("PHH"));
+        assert_eq!(site("STATE:0:r200c/cc/cc/cc:9d6c|KhQh/3s8hTd/2c/Ac:-200|200"), Some("ACPC"));
+
+        // Text that is not a hand history
+        for text in ["", "Game summary\nTotal pot 20", "PokerStars Tournament Summary", "<session></session>", "variants = 3"] {
+            assert_eq!(site(text), None, "{}", text);
+        }
+        assert!(parse_string("").is_empty());
+
+        // A header past the detected head is still read as PokerStars
+        let preamble = format!("{}{}", "\n".repeat(3000), stars);
+        assert_eq!(site(&preamble), None);
+        let hands = parse_string(&preamble);
+        assert_eq!(hands.len(), 1);
+        assert_eq!(hands[0].header.players.len(), 2);
+    }
 }
\ No newline at end of file
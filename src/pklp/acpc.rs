@@ -0,0 +1,438 @@
+//region ACPC
+
+// Annual Computer Poker Competition dealer logs
+//
+// STATE:0:fr200fffr900c/cc/r2150c/cc:9d6c|AsKs|7d9h|6d6h|Ah4s|2dJc/Qs7h5s/4h/7c:-50|-2150|0|2200|0|0:MrWhite|Gogo|Budd|Eddie|Bill|Pluribus
+// SCORE:-50|-2150|0|2200|0|0:MrWhite|Gogo|Budd|Eddie|Bill|Pluribus
+//
+// Players are listed from the seat after the button. No-limit raises are the total the player
+// put in the pot during the hand, limit raises have no amount. Blinds and stacks are only in the
+// game definition.
+
+use super::*;
+
+pub struct Acpc;
+
+impl SiteParser for Acpc {
+    fn name(&self) -> &'static str { "ACPC" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        parse_acpc(data, &AcpcGame::parse(data))
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("STATE:")
+}
+
+#[derive(Debug, Clone)]
+pub struct AcpcGame {
+    pub limit: bool,
+    pub stacks: Vec<Float>,
+    pub blinds: Vec<Float>,
+    pub raise_sizes: Vec<Float>,
+    pub first_player: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl AcpcGame {
+    // GAMEDEF
+    // limit
+    // numPlayers = 2
+    // stack = 20000 20000
+    // blind = 100 50
+    // raiseSize = 100 100 200 200
+    // firstPlayer = 2 1 1 1
+    // END GAMEDEF
+    //
+    // Without a definition the 6-max no-limit game of the Pluribus logs is used.
+    pub fn parse(def: &str) -> AcpcGame {
+        let mut game = AcpcGame{limit: false, stacks: Vec::new(), blinds: Vec::new(), raise_sizes: Vec::new(), first_player: Vec::new()};
+        let Some(start) = def.find("GAMEDEF") else { return game };
+        let numbers = |v: &str| v.split_whitespace().filter_map(|n| n.parse::<Float>().ok()).collect::<Vec<_>>();
+        for line in def[start..].lines().skip(1).take_while(|l| !l.starts_with("END GAMEDEF")) {
+            let (key, value) = line.split_once('=').unwrap_or((line, ""));
+            match key.trim().to_lowercase().as_str() {
+                "limit" => game.limit = true,
+                "nolimit" => game.limit = false,
+                "stack" => game.stacks = numbers(value),
+                "blind" => game.blinds = numbers(value),
+                "raisesize" => game.raise_sizes = numbers(value),
+                "firstplayer" => game.first_player = numbers(value).iter().map(|p| *p as u8).collect(),
+                _ => {}
+            }
+        }
+        game
+    }
+
+    fn stack(&self, i: usize) -> Float {
+        self.stacks.get(i).or(self.stacks.last()).copied().unwrap_or(10000.0)
+    }
+
+    fn blinds(&self, n: usize) -> Vec<Float> {
+        match (self.blinds.is_empty(), n) {
+            (false, _) => self.blinds.clone(),
+            // The big blind is first in heads-up
+            (true, 2) => vec![100.0, 50.0],
+            (true, _) => vec![50.0, 100.0],
+        }
+    }
+
+    fn first_player(&self, round: usize, n: usize) -> usize {
+        match self.first_player.get(round) {
+            Some(p) => (*p as usize).saturating_sub(1) % n,
+            None if round > 0 => 0,
+            None if n == 2 => 1,
+            None => 2 % n,
+        }
+    }
+}
+
+//region Write
+
+fn is_limit(game_type: &str) -> bool {
+    let g = game_type.to_lowercase();
+    g.contains("limit") && !g.contains("no limit") && !g.contains("pot limit")
+}
+
+// Chips each player won less what they put in the pot, antes included and uncalled bets excluded
+fn net<'d>(hand: &Hand, data: &'d str) -> HashMap<&'d str, Float> {
+    let mut net = HashMap::new();
+    let preflop = hand.header.actions.iter().chain(hand.hole_cards.actions.iter()).collect::<Vec<_>>();
+    let rounds = std::iter::once(preflop).chain(hand.streets.iter().map(|s| s.actions.iter().collect()));
+    for actions in rounds {
+        let mut committed: HashMap<&str, Float> = HashMap::new();
+        let mut to_call: Float = 0.0;
+        for a in actions {
+            let (name, added, live) = match a {
+                Action::Blind(BlindType::Ante, name, amount) => (name, amount.amount, 0.0),
+                // The small blind part is dead
+                Action::Blind(BlindType::SmallAndBig, name, amount) => (name, amount.amount, amount.amount - hand.header.info.buy_in_min.amount),
+                Action::Blind(_, name, amount) | Action::Play(Play::Call | Play::Bet, name, Some(ActionData{bet: amount, ..})) => (name, amount.amount, amount.amount),
+                Action::Play(Play::Raise, name, Some(d)) => {
+                    let c = committed.get(&data[*name]).copied().unwrap_or(0.0);
+                    let to = d.bet_to.map_or(to_call + d.bet.amount, |to| to.amount);
+                    (name, to - c, to - c)
+                }
+                Action::UncalledBetReturned(name, amount) => (name, -amount.amount, 0.0),
+                Action::CollectedPot(_, name, amount, _) => (name, -amount.amount, 0.0),
+                _ => continue,
+            };
+            let c = committed.entry(&data[*name]).or_insert(0.0);
+            *c += live;
+            to_call = to_call.max(*c);
+            *net.entry(&data[*name]).or_insert(0.0) -= added;
+        }
+    }
+    net
+}
+
+#[allow(dead_code)]
+pub fn acpc_state(hand: &Hand, number: usize, data: &str) -> String {
+    let players = hand.players_after_button();
+    let index = |name: &Span| players.iter().position(|p| data[p.name] == data[*name]);
+    let limit = is_limit(&data[hand.header.info.game_type]);
+
+    // Raises are written as the total put in the pot during the hand
+    let mut total = vec![0.0; players.len()];
+    let mut committed = vec![0.0; players.len()];
+    let mut rounds = vec![String::new()];
+    for a in &hand.header.actions {
+        if let (Action::Blind(t, name, amount), false) = (a, matches!(a, Action::Blind(BlindType::Ante, ..))) {
+            let live = if let BlindType::SmallAndBig = t { amount.amount - hand.header.info.buy_in_min.amount } else { amount.amount };
+            if let Some(i) = index(name) {
+                total[i] += live;
+                committed[i] += live;
+            }
+        }
+    }
+    let streets = hand.streets.iter().filter(|s| s.index == 0 && matches!(s.t, StreetType::Flop | StreetType::Turn | StreetType::River));
+    for (n, actions) in std::iter::once(&hand.hole_cards.actions).chain(streets.map(|s| &s.actions)).enumerate() {
+        if n > 0 {
+            rounds.push(String::new());
+            committed.iter_mut().for_each(|c| *c = 0.0);
+        }
+        let round = rounds.last_mut().unwrap();
+        for a in actions.iter() {
+            let Some((name, d)) = (match a {
+                Action::Fold(name, _) => Some((name, None)),
+                Action::Play(Play::Check, name, _) => Some((name, None)),
+                Action::Play(Play::Call | Play::Bet | Play::Raise, name, d) => Some((name, *d)),
+                _ => None,
+            }) else { continue };
+            let Some(i) = index(name) else { continue };
+            match (a, d) {
+                (Action::Fold(..), _) => round.push('f'),
+                (Action::Play(Play::Call, ..), Some(d)) => {
+                    total[i] += d.bet.amount;
+                    committed[i] += d.bet.amount;
+                    round.push('c');
+                }
+                (Action::Play(Play::Check, ..), _) => round.push('c'),
+                (Action::Play(play, ..), Some(d)) => {
+                    let max = committed.iter().fold(0.0, |a: Float, b| a.max(*b));
+                    let to = match (play, d.bet_to) {
+                        (Play::Raise, Some(to)) => to.amount,
+                        (Play::Raise, None) => max + d.bet.amount,
+                        _ => committed[i] + d.bet.amount,
+                    };
+                    total[i] += to - committed[i];
+                    committed[i] = to;
+                    round.push('r');
+                    if !limit { round.push_str(&format_amount(total[i])); }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let known = hand.known_hole_cards(data);
+    let cards = |cards: &[Card]| cards.iter().map(|c| c.to_string()).collect::<String>();
+    let mut dealt = players.iter().map(|p| known.get(&data[p.name]).map_or_else(String::new, |c| cards(c))).collect::<Vec<_>>().join("|");
+    if let Some(board) = hand.summary.boards.first() {
+        for range in [0..3, 3..4, 4..5].into_iter().filter(|r| r.end <= board.len()) {
+            dealt.push('/');
+            dealt.push_str(&cards(&board[range]));
+        }
+    }
+
+    let net = net(hand, data);
+    let scores = players.iter().map(|p| format_amount(net.get(&data[p.name]).copied().unwrap_or(0.0))).collect::<Vec<_>>().join("|");
+    let names = players.iter().map(|p| &data[p.name]).collect::<Vec<_>>().join("|");
+
+    format!("STATE:{}:{}:{}:{}:{}", number, rounds.join("/"), dealt, scores, names)
+}
+
+#[allow(dead_code)]
+pub fn to_acpc(hands: &[Hand], data: &str) -> String {
+    let mut out = String::new();
+    let mut totals: Vec<(&str, Float)> = Vec::new();
+    for (number, hand) in hands.iter().enumerate() {
+        let net_by_name = net(hand, data);
+        for p in &hand.header.players {
+            let name = &data[p.name];
+            let net = net_by_name.get(name).copied().unwrap_or(0.0);
+            match totals.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, total)) => *total += net,
+                None => totals.push((name, net)),
+            }
+        }
+        writeln!(out, "{}", acpc_state(hand, number, data)).unwrap();
+    }
+    let scores = totals.iter().map(|(_, t)| format_amount(*t)).collect::<Vec<_>>().join("|");
+    let names = totals.iter().map(|(n, _)| *n).collect::<Vec<_>>().join("|");
+    writeln!(out, "SCORE:{}:{}", scores, names).unwrap();
+    out
+}
+
+//endregion
+
+//region Read
+
+pub fn parse_acpc(data: &str, game: &AcpcGame) -> Vec<Hand> {
+    data.lines().filter(|l| is_hand_header(l)).filter_map(|l| parse_state(l.trim_start_matches('\u{feff}').trim_end(), game, data)).collect()
+}
+
+fn parse_state(line: &str, game: &AcpcGame, data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut fields = line.split(':');
+    let hand = fields.next()?;
+    let id = fields.next()?;
+    let betting = fields.next()?;
+    let cards = fields.next()?;
+    let scores = fields.next()?.split('|').map(|s| s.parse::<Float>().unwrap_or(0.0)).collect::<Vec<_>>();
+    let names = fields.next()?.split('|').collect::<Vec<_>>();
+    let n = names.len();
+
+    let mut card_rounds = cards.split('/');
+    let hole = card_rounds.next()?.split('|').map(parse_acpc_cards).collect::<Vec<_>>();
+    if hole.len() != n { return None; }
+    let blinds = game.blinds(n);
+    let stacks = (0..n).map(|i| game.stack(i)).collect::<Vec<_>>();
+
+    let mut players = PlayerVec::new();
+    for (i, name) in names.iter().enumerate() {
+        players.push(Player{name: span(name), seat: i as u8 + 1, chips: Currency{symbol: NO_CURRENCY, amount: stacks[i]}, bounty: None});
+    }
+    let big_blind = blinds.iter().fold(0.0, |a: Float, b| a.max(*b));
+    let small_blind = blinds.iter().filter(|b| **b > 0.0).fold(big_blind, |a: Float, b| a.min(*b));
+    let info = HandInfo{
+        site_name: "ACPC", site: span(&hand[..0]), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(&hand[..0]),
+        buy_in_min: Currency{symbol: NO_CURRENCY, amount: small_blind},
+        buy_in_max: Currency{symbol: NO_CURRENCY, amount: big_blind},
+    };
+    let table = Table{name: span(&hand[..0]), max_players: n as u8, button: n as u8, play_money: false};
+    let header = Header{info, table, players, actions: ActionVec::new()};
+
+    let mut b = HandBuilder::default();
+    let mut total = vec![0.0; n];
+    let mut posted = blinds.iter().copied().enumerate().filter(|(i, b)| *b > 0.0 && *i < n).collect::<Vec<_>>();
+    posted.sort_by(|a, b| a.1.total_cmp(&b.1));
+    for &(i, blind) in &posted {
+        let t = if blind < big_blind { BlindType::Small } else { BlindType::Big };
+        total[i] += blind;
+        b.blind(names[i], span(names[i]), t, Currency{symbol: NO_CURRENCY, amount: blind});
+    }
+    b.hole_cards();
+    for (i, cards) in hole.iter().enumerate().filter(|(i, c)| !c.is_empty() && *i < n) {
+        b.hole_cards().dealt_to.push((span(names[i]), cards.clone()));
+    }
+
+    let mut folded = vec![false; n];
+    for (round, actions) in betting.split('/').enumerate() {
+        if round > 0 {
+            let t = [StreetType::Flop, StreetType::Turn, StreetType::River][(round - 1).min(2)];
+            b.deal_board(t, parse_acpc_cards(card_rounds.next().unwrap_or("")));
+        }
+        let can_act = |i: usize, folded: &[bool], total: &[Float]| !folded[i] && total[i] < stacks[i];
+        let mut i = game.first_player(round, n);
+        let mut rest = actions;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            for _ in 0..n {
+                if can_act(i, &folded, &total) { break; }
+                i = (i + 1) % n;
+            }
+            let (name, name_span) = (names[i], span(names[i]));
+            let committed = b.committed.get(name).copied().unwrap_or(0.0);
+            let remaining = stacks[i] - total[i];
+            match c {
+                'f' => {
+                    folded[i] = true;
+                    b.push(Action::Fold(name_span, None));
+                }
+                'c' => {
+                    let added = (b.to_call - committed).min(remaining);
+                    if added <= 0.0 {
+                        b.push(Action::Play(Play::Check, name_span, None));
+                    } else {
+                        total[i] += added;
+                        b.play(name, name_span, Currency{symbol: NO_CURRENCY, amount: added}, added >= remaining);
+                    }
+                }
+                'r' => {
+                    let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+                    let added = if game.limit {
+                        b.to_call - committed + game.raise_sizes.get(round).copied().unwrap_or(big_blind)
+                    } else {
+                        rest[..end].parse::<Float>().unwrap_or(0.0) - total[i]
+                    };
+                    rest = &rest[end..];
+                    total[i] += added;
+                    b.play(name, name_span, Currency{symbol: NO_CURRENCY, amount: added}, added >= remaining);
+                }
+                _ => continue,
+            }
+            i = (i + 1) % n;
+        }
+    }
+
+    // Hole cards are revealed for every player still in the hand
+    let live = (0..n).filter(|i| !folded[*i]).collect::<Vec<_>>();
+    if live.len() > 1 {
+        for &i in &live {
+            if !hole[i].is_empty() {
+                b.showdown();
+                b.push(Action::ShowHand(span(names[i]), hole[i].clone()));
+            }
+        }
+    }
+    for (i, score) in scores.iter().enumerate().filter(|(i, _)| *i < n) {
+        let won = score + total[i];
+        if won > 0.0 {
+            b.push(Action::CollectedPot(Pot::Main, span(names[i]), Currency{symbol: NO_CURRENCY, amount: won}, None));
+        }
+    }
+    Some(b.finish(header, None, data))
+}
+
+fn parse_acpc_cards(s: &str) -> Cards {
+    // 9d6c, Qs7h5s
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut cards = Cards::new();
+    for c in chars.chunks_exact(2) {
+        if "23456789TJQKA".contains(c[0]) && "shdc".contains(c[1]) {
+            cards.extend(parse_cards(&c.iter().collect::<String>()));
+        }
+    }
+    cards
+}
+
+//endregion
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluribus_round_trip() {
+        let data = std::fs::read_to_string("data/example/pluribus_example.txt").unwrap();
+        let hands = parse_string(&data);
+        let state = acpc_state(&hands[0], 0, &data);
+        assert_eq!(state, "STATE:0:fr200fffr900c/cc/r2150c/cc:9d6c|AsKs|7d9h|6d6h|Ah4s|2dJc/Qs7h5s/4h/7c:-50|-2150|0|2200|0|0:MrWhite|Gogo|Budd|Eddie|Bill|Pluribus");
+
+        let log = to_acpc(&hands, &data);
+        assert_eq!(log.lines().count(), hands.len() + 1);
+        assert!(log.lines().last().unwrap().starts_with("SCORE:"));
+        assert!(detect_site(&log).is_some_and(|site| site.name() == "ACPC"));
+        let read = parse_string(&log);
+        assert_eq!(read.len(), hands.len());
+        for (number, hand) in read.iter().enumerate() {
+            assert_eq!(acpc_state(hand, number, &log), log.lines().nth(number).unwrap());
+        }
+        assert_eq!(read[0].summary.pot.amount, 4350.0);
+        assert!(matches!(read[0].streets[3].actions[0], Action::ShowHand(..)));
+    }
+
+    #[test]
+    fn limit_heads_up() {
+        let data = "GAMEDEF
+limit
+numPlayers = 2
+numRounds = 4
+blind = 10 5
+raiseSize = 10 10 20 20
+firstPlayer = 2 1 1 1
+END GAMEDEF
+STATE:7:rrc/crc/cc/rc:Ah2c|KsKd/Qs7h5s/4h/7c:-60|60:alice|bob
+";
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(hand.header.table.button, 2);
+        assert!(matches!(hand.header.actions[0], Action::Blind(BlindType::Small, name, _) if &data[name] == "bob"));
+        let raise = |a: &Action| match a {
+            Action::Play(Play::Raise, _, Some(d)) => (d.bet.amount, d.bet_to.unwrap().amount),
+            a => panic!("Unexpected action {:?}", a),
+        };
+        assert_eq!(raise(&hand.hole_cards.actions[0]), (10.0, 20.0));
+        assert_eq!(raise(&hand.hole_cards.actions[1]), (10.0, 30.0));
+        assert!(matches!(hand.streets[0].actions[1], Action::Play(Play::Bet, name, Some(ActionData{bet: Currency{amount, ..}, ..})) if &data[name] == "bob" && amount == 10.0));
+        assert_eq!(hand.summary.pot.amount, 120.0);
+        assert_eq!(net(hand, data)["bob"], 60.0);
+        assert_eq!(net(hand, data)["alice"], -60.0);
+    }
+
+    #[test]
+    fn malformed_states() {
+        let data = "STATE:0:cc/cc/cc/cc:AsKs/Qs7h5s/4h/7c:0|0:alice|bob
+STATE:1:cé:AsKs|QhQd:0|0:alice|bob
+";
+        let hands = parse_acpc(data, &AcpcGame::parse(data));
+        assert_eq!(hands.len(), 1);
+        assert_eq!(&data[hands[0].header.info.id], "1");
+    }
+}
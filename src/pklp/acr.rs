@@ -0,0 +1,255 @@
+//region ACR
+
+// Americas Cardroom and the other Winning Poker Network skins:
+//
+// Game Hand #2040150034 - Holdem(No Limit) - $0.25/$0.50 - 2020/02/08 04:01:19 UTC
+// Acamar 6-max Seat #3 is the button
+// Seat 1: pondtree312 ($49.50)
+// pondtree312 posts the small blind $0.25
+// *** HOLE CARDS ***
+// Main pot $0.75
+// spr1teg raises $1.25 to $1.25
+// spr1teg shows [8s Jd] (a pair of Eights)
+// spr1teg collected $8.85 from main pot
+
+use super::*;
+
+pub struct Acr;
+
+impl SiteParser for Acr {
+    fn name(&self) -> &'static str { "ACR" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        hand_blocks(data, is_hand_header).iter().filter_map(|lines| parse_hand(lines, data)).collect()
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("Game Hand #")
+}
+
+fn parse_hand(lines: &[&str], data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut lines = lines.iter().copied().peekable();
+
+    // Game Hand #2040150034 - Holdem(No Limit) - $0.25/$0.50 - 2020/02/08 04:01:19 UTC
+    let title = lines.next()?.trim_start_matches('\u{feff}');
+    let mut parts = title.split(" - ");
+    let (site, rest) = parts.next()?.split_once(' ')?;
+    let (hand, id) = rest.split_once(" #")?;
+    let game_type = parts.next()?;
+    let (buy_in_min, buy_in_max) = parse_stakes(parts.next()?)?;
+
+    // Acamar 6-max Seat #3 is the button
+    let table_line = lines.next()?;
+    let button = table_line.rfind('#').and_then(|i| parse_integer(&table_line[i + 1..])).map_or(0, |(v, _)| v as u8);
+    let table_name = &table_line[..table_line.find(" Seat #").unwrap_or(table_line.len())];
+    let (table_name, max_players) = match table_name.rsplit_once(' ') {
+        Some((name, max)) if max.ends_with("-max") => (name, parse_integer(max).map_or(0, |(v, _)| v as u8)),
+        _ => (table_name, 0),
+    };
+
+    let mut b = HandBuilder::default();
+    while lines.peek().is_some_and(|line| b.seat(line, data)) {
+        lines.next();
+    }
+
+    let info = HandInfo{
+        site_name: "ACR", site: span(site), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(game_type), buy_in_min, buy_in_max,
+    };
+    let table = Table{name: span(table_name), max_players, button, play_money: false};
+    let header = Header{info, table, players: PlayerVec::new(), actions: ActionVec::new()};
+
+    let mut rake = None;
+    let mut in_summary = false;
+    for line in lines {
+        if let Some(section) = line.strip_prefix("*** ") {
+            match section.split(" ***").next()? {
+                "HOLE CARDS" => { b.hole_cards(); }
+                "FLOP" => b.deal_board(StreetType::Flop, parse_cards(line.rbetween('[', ']'))),
+                "TURN" => b.deal_board(StreetType::Turn, parse_cards(line.rbetween('[', ']'))),
+                "RIVER" => b.deal_board(StreetType::River, parse_cards(line.rbetween('[', ']'))),
+                "SHOW DOWN" => b.showdown(),
+                "SUMMARY" => in_summary = true,
+                _ => {}
+            }
+        }
+        else if in_summary {
+            if let Some(total) = line.strip_prefix("Total pot ") {
+                b.total = total.split(' ').next().and_then(parse_currency_any);
+                rake = total.split(" | ").find_map(|f| f.strip_prefix("Rake ")).and_then(parse_currency_any);
+            }
+        }
+        else if line.starts_with("Main pot ") {
+            // Running pot totals written at the start of every street
+        }
+        else if let Some((name, rest)) = b.player(line, data) {
+            parse_action(&mut b, span(name), name, rest);
+        }
+        else if !b.dealt(line, data) {
+            // Uncalled bet ($0.75) returned to spr1teg
+            b.uncalled(line, data);
+        }
+    }
+
+    Some(b.finish(header, rake, data))
+}
+
+fn parse_action<'a>(b: &mut HandBuilder<'a>, span: Span, name: &'a str, rest: &str) {
+    // Amounts end the line: "posts the big blind $0.50", "raises $3 to $3 and is all-in"
+    let all_in = rest.ends_with(" and is all-in");
+    let rest = rest.trim_end_matches(" and is all-in");
+    let amount = || rest.rsplit(' ').next().and_then(parse_currency_any);
+    if let Some(blind) = rest.strip_prefix("posts ") {
+        let t = if blind.starts_with("the small blind") {
+            BlindType::Small
+        } else if blind.starts_with("ante") {
+            BlindType::Ante
+        } else if blind.starts_with("dead") {
+            BlindType::SmallAndBig
+        } else {
+            BlindType::Big
+        };
+        if let Some(amount) = amount() { b.blind(name, span, t, amount); }
+    }
+    else if rest.starts_with("calls ") || rest.starts_with("bets ") {
+        if let Some(amount) = amount() { b.play(name, span, amount, all_in); }
+    }
+    else if rest.starts_with("raises ") {
+        if let Some(to) = amount() { b.raise_to(name, span, to, all_in); }
+    }
+    else if let Some(won) = rest.strip_prefix("collected ") {
+        // collected $8.85 from main pot, collected $1 from side pot-1
+        let pot = parse_pot(won);
+        if let Some(amount) = won.split(' ').next().and_then(parse_currency_any) {
+            b.push(Action::CollectedPot(pot, span, amount, None));
+        }
+    }
+    else {
+        b.common_action(span, rest);
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_acr_hand() {
+        let data = "Game Hand #2040150034 - Holdem(No Limit) - $0.25/$0.50 - 2020/02/08 04:01:19 UTC
+Acamar 6-max Seat #3 is the button
+Seat 1: pondtree312 ($49.50)
+Seat 2: Rifama ($50)
+Seat 3: spr1teg ($50.50)
+pondtree312 posts the small blind $0.25
+Rifama posts the big blind $0.50
+*** HOLE CARDS ***
+Main pot $0.75
+Dealt to spr1teg [8s Jd]
+spr1teg raises $1.25 to $1.25
+pondtree312 calls $1
+Rifama folds
+*** FLOP *** [8d 3c Ad]
+Main pot $3
+pondtree312 checks
+spr1teg bets $1
+pondtree312 raises $3 to $3
+spr1teg calls $2
+*** TURN *** [8d 3c Ad] [9c]
+Main pot $9
+pondtree312 checks
+spr1teg checks
+*** RIVER *** [8d 3c Ad 9c] [6d]
+Main pot $9
+pondtree312 checks
+spr1teg checks
+*** SHOW DOWN ***
+Main pot $8.85
+pondtree312 shows [5d Ks] (High Card)
+spr1teg shows [8s Jd] (a pair of Eights)
+spr1teg collected $8.85 from main pot
+*** SUMMARY ***
+Total pot $9 | Rake $0.15
+Board [8d 3c Ad 9c 6d]
+Seat 3: spr1teg (button) showed [8s Jd] and won $8.85 with a pair of Eights
+";
+        assert!(detect_site(data).is_some_and(|site| site.name() == "ACR"));
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.info.id], "2040150034");
+        assert_eq!(&data[hand.header.info.game_type], "Holdem(No Limit)");
+        assert_eq!(hand.header.info.buy_in_max.amount, 0.5);
+        assert_eq!(&data[hand.header.table.name], "Acamar");
+        assert_eq!(hand.header.table.max_players, 6);
+        assert_eq!(hand.header.table.button, 3);
+        assert_eq!(&data[hand.hero().unwrap()], "spr1teg");
+        assert_eq!(hand.streets.len(), 4);
+        assert_eq!(hand.summary.rake.unwrap().amount, 0.15);
+        assert_eq!(hand.summary.pot.amount, 9.0);
+        assert_eq!(hand.summary.seats.len(), 2);
+    }
+
+    #[test]
+    fn action_grammar() {
+        let data = "Game Hand #2040150035 - Holdem(No Limit) - $0.25/$0.50 - 2020/02/08 04:02:19 UTC
+Acamar 6-max Seat #1 is the button
+Seat 1: pondtree312 ($10)
+Seat 2: Rifama ($50)
+Seat 3: spr1teg posts ($50)
+Rifama posts the small blind $0.25
+spr1teg posts posts the big blind $0.50
+pondtree312 posts ante $0.05
+*** HOLE CARDS ***
+pondtree312 raises $2 to $2
+Rifama raises $8 to $8
+spr1teg posts folds
+pondtree312 raises $9.95 to $9.95 and is all-in
+Rifama calls $1.95
+*** FLOP *** [8d 3c Ad]
+Rifama bets $5
+Uncalled bet ($5) returned to Rifama
+*** SHOW DOWN ***
+pondtree312 shows [Ah Kd] (a pair of Aces)
+Rifama mucks
+pondtree312 collected $20.40 from main pot
+";
+        let hands = parse_string(data);
+        let hand = &hands[0];
+        let actions = hand.actions().collect::<Vec<_>>();
+        assert!(matches!(actions[0], Action::Blind(BlindType::Small, _, Currency{amount, ..}) if *amount == 0.25));
+        assert!(matches!(actions[1], Action::Blind(BlindType::Big, name, _) if &data[*name] == "spr1teg posts"));
+        assert!(matches!(actions[2], Action::Blind(BlindType::Ante, _, _)));
+
+        // Raises are written as the total, not the increment
+        match actions[4] {
+            Action::Play(Play::Raise, _, Some(d)) => {
+                assert_eq!(d.bet.amount, 6.0);
+                assert_eq!(d.bet_to.unwrap().amount, 8.0);
+            }
+            a => panic!("Unexpected action {:?}", a),
+        }
+        assert!(matches!(actions[5], Action::Fold(name, None) if &data[*name] == "spr1teg posts"));
+        assert!(matches!(actions[6], Action::Play(Play::Raise, _, Some(ActionData{all_in: true, ..}))));
+        assert!(matches!(actions[7], Action::Play(Play::Call, _, Some(ActionData{bet: Currency{amount, ..}, ..})) if *amount == 1.95));
+        assert!(matches!(actions[9], Action::UncalledBetReturned(_, Currency{amount, ..}) if *amount == 5.0));
+
+        // Showdown lines have no colon after the name
+        assert!(matches!(actions[10], Action::ShowHand(name, _) if &data[*name] == "pondtree312"));
+        assert!(matches!(actions[11], Action::Event(Event::NotShow, _)));
+        assert!(matches!(actions[12], Action::CollectedPot(Pot::Main, _, _, None)));
+    }
+}
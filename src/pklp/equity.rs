@@ -0,0 +1,232 @@
+//region Equity
+
+// Chance of each hand to win the pot once the players are all-in, over the cards still to come:
+//
+// equity(Game::Holdem, &[parse_cards("Ah Ad"), parse_cards("Kh Kd")], &[]) == [0.82, 0.18]
+//
+// Boards with few runouts left are enumerated, the others are sampled with a fixed seed so the same hand
+// always gives the same numbers. Ties share the pot, hi/lo pots are split when a low qualifies.
+
+use super::*;
+
+// Runouts enumerated before sampling takes over, all boards from the flop on
+const EXHAUSTIVE_LIMIT: u64 = 50_000;
+const SAMPLES: usize = 20_000;
+
+#[allow(dead_code)]
+pub fn equity(game: Game, hands: &[Cards], board: &[Card]) -> Vec<Float> {
+    equities(game, hands, board, &[(0..hands.len()).collect()]).remove(0)
+}
+
+// Share of every pot won by each hand, pots list the indices of the hands contesting them
+pub fn equities(game: Game, hands: &[Cards], board: &[Card], pots: &[Vec<usize>]) -> Vec<Vec<Float>> {
+    let known = hands.iter().flat_map(|h| h.iter()).chain(board).collect::<Vec<_>>();
+    let deck = (1..=13).flat_map(|n| [Suit::Spade, Suit::Club, Suit::Heart, Suit::Diamond].into_iter().map(move |suit| Card{n, suit}))
+        .filter(|c| !known.iter().any(|k| k.n == c.n && k.suit as u8 == c.suit as u8))
+        .collect::<Vec<_>>();
+    let missing = 5usize.saturating_sub(board.len());
+
+    let mut shares = vec![vec![0.0f64; hands.len()]; pots.len()];
+    let mut runouts = 0usize;
+    let mut full = board.to_vec();
+    let mut score = |runout: &[Card]| {
+        full.truncate(board.len());
+        full.extend_from_slice(runout);
+        let strengths = hands.iter().map(|h| game.evaluate(h, &full)).collect::<Vec<_>>();
+        for (pot, shares) in pots.iter().zip(shares.iter_mut()) {
+            award(pot, &strengths, shares);
+        }
+        runouts += 1;
+    };
+
+    if binomial(deck.len() as u64, missing as u64) <= EXHAUSTIVE_LIMIT {
+        combinations(&deck, missing, &mut Vec::new(), &mut score);
+    } else {
+        let mut deck = deck;
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..SAMPLES {
+            // Partial Fisher-Yates, the first cards of the deck are the runout
+            for i in 0..missing {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                let j = i + (seed % (deck.len() - i) as u64) as usize;
+                deck.swap(i, j);
+            }
+            score(&deck[..missing]);
+        }
+    }
+
+    shares.into_iter().map(|s| s.into_iter().map(|v| (v / runouts.max(1) as f64) as Float).collect()).collect()
+}
+
+// Adds the shares of one runout, the high half goes to the best high hands and the low half to the best lows
+fn award(pot: &[usize], strengths: &[Strength], shares: &mut [f64]) {
+    let best_high = pot.iter().filter_map(|i| strengths[*i].high).max();
+    let best_low = pot.iter().filter_map(|i| strengths[*i].low).max();
+    let halves = if best_high.is_some() && best_low.is_some() { 0.5 } else { 1.0 };
+    let mut split = |winners: Vec<usize>| {
+        let n = winners.len() as f64;
+        winners.into_iter().for_each(|i| shares[i] += halves / n);
+    };
+    if let Some(high) = best_high {
+        split(pot.iter().copied().filter(|i| strengths[*i].high == Some(high)).collect());
+    }
+    if let Some(low) = best_low {
+        split(pot.iter().copied().filter(|i| strengths[*i].low == Some(low)).collect());
+    }
+}
+
+fn combinations(deck: &[Card], k: usize, combo: &mut Vec<Card>, f: &mut impl FnMut(&[Card])) {
+    if combo.len() == k {
+        return f(combo);
+    }
+    for i in 0..deck.len() {
+        combo.push(deck[i]);
+        combinations(&deck[i + 1..], k, combo, f);
+        combo.pop();
+    }
+}
+
+fn binomial(n: u64, k: u64) -> u64 {
+    (0..k.min(n)).fold(1, |c, i| c * (n - i) / (i + 1))
+}
+
+//endregion
+
+//region Equity - All-in
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AllIn {
+    // Board when the betting closed
+    pub board: Cards,
+    // Equity of the players left in the main pot
+    pub equity: Vec<(Span, Float)>,
+    // Net result of every player with the pots shared by equity instead of the runout, in seat order
+    pub ev: Vec<(Span, Currency)>,
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // None without an all-in called before the river, in games without a board and when a player's cards are unknown
+    pub fn all_in_equity(&self, data: &str) -> Option<AllIn> {
+        let game = self.game(data).filter(|g| g.has_board())?;
+
+        let mut state = HandState::new(self, data);
+        let board = loop {
+            state.next()?;
+            let in_hand = state.players.iter().filter(|p| !p.folded).count();
+            if state.to_act().is_none() && state.all_in().count() > 0 && state.active().count() <= 1 && in_hand > 1 {
+                break state.street().map_or_else(Cards::new, |s| {
+                    let mut cards = s.cards.clone().unwrap_or_default();
+                    cards.extend(s.new_card);
+                    cards
+                });
+            }
+        };
+        if board.len() >= 5 { return None; }
+
+        let pots = self.pots(data);
+        let known = self.known_hole_cards(data);
+        let mut players: Vec<Span> = Vec::new();
+        for name in pots.iter().flat_map(|p| &p.eligible) {
+            if !players.iter().any(|p| data[*p] == data[*name]) {
+                players.push(*name);
+            }
+        }
+        let hands = players.iter().map(|p| known.get(&data[*p]).cloned()).collect::<Option<Vec<_>>>()?;
+        let contested = pots.iter()
+            .map(|pot| players.iter().enumerate().filter(|(_, p)| pot.eligible.iter().any(|e| data[*e] == data[**p])).map(|(i, _)| i).collect())
+            .collect::<Vec<Vec<usize>>>();
+        let shares = equities(game, &hands, &board, &contested);
+
+        // Pots are shared after the rake and the fees taken from them
+        let summary = &self.summary;
+        let taken = summary.rake.map_or(0.0, |r| r.amount) + summary.jackpot.map_or(0.0, |j| j.amount) + summary.fees.iter().map(|(_, f)| f.amount).sum::<Float>();
+        let paid_out = if summary.pot.amount > 0.0 { 1.0 - taken / summary.pot.amount } else { 1.0 };
+
+        let symbol = summary.pot.symbol;
+        let ev = self.results(data).into_iter().map(|r| {
+            let net = match players.iter().position(|p| data[*p] == data[r.name]) {
+                Some(i) => pots.iter().zip(&shares).map(|(pot, s)| s[i] * pot.amount.amount * paid_out).sum::<Float>() - r.invested.amount,
+                None => r.net.amount,
+            };
+            (r.name, Currency{symbol, amount: (net * 10000.0).round() / 10000.0})
+        }).collect();
+
+        let equity = players.iter().zip(&shares[0]).map(|(p, e)| (*p, *e)).collect();
+        Some(AllIn{board, equity, ev})
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerated_and_sampled() {
+        let cards = |s: &str| parse_cards(s);
+        let close = |a: Float, b: Float, d: Float| assert!((a - b).abs() < d, "{} {}", a, b);
+
+        let preflop = equity(Game::Holdem, &[cards("Ah Ad"), cards("Kh Kd")], &[]);
+        close(preflop[0], 0.8195, 0.01);
+        close(preflop[0] + preflop[1], 1.0, 0.0001);
+
+        // 8 outs twice for the straight draw, runner-runner flushes and two pairs
+        let flop = equity(Game::Holdem, &[cards("As Kd"), cards("9c 8c")], &cards("Ah Tc 7d"));
+        close(flop[1], 0.3535, 0.0001);
+        let turn = equity(Game::Holdem, &[cards("As Kd"), cards("9c 8c")], &cards("Ah Tc 7d 2s"));
+        close(turn[1], 8.0 / 44.0, 0.0001);
+
+        let chop = equity(Game::Holdem, &[cards("2c 3d"), cards("2h 3s")], &cards("Ah Kc Qd Js"));
+        assert_eq!(chop, [0.5, 0.5]);
+
+        // Nut low against a high hand without a low draw on the turn
+        let hi_lo = equity(Game::OmahaHiLo, &[cards("Ac 2c Kh Kd"), cards("Qs Qh Js Jh")], &cards("Qd 5s 4h 9c"));
+        assert!(hi_lo[0] > 0.2 && hi_lo[1] > 0.5);
+    }
+
+    #[test]
+    fn all_in_ev() {
+        let data = "PokerStars Hand #208966141603:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: pondtree312 ($20 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+Dealt to pondtree312 [As Kd]
+spr1teg: folds
+pondtree312: raises $1 to $1.50
+Rifama: calls $1
+*** FLOP *** [Ah Tc 7d]
+pondtree312: bets $2
+Rifama: raises $16.50 to $18.50
+pondtree312: calls $16.50 and is all-in
+*** TURN *** [Ah Tc 7d] [2s]
+*** RIVER *** [Ah Tc 7d 2s] [Jc]
+*** SHOW DOWN ***
+Rifama: shows [9c 8c] (high card Ace)
+pondtree312: shows [As Kd] (a pair of Aces)
+pondtree312 collected $40 from pot
+*** SUMMARY ***
+Total pot $40 | Rake $0
+Board [Ah Tc 7d 2s Jc]
+";
+        let hands = parse_string(data);
+        let all_in = hands[0].all_in_equity(data).unwrap();
+        assert_eq!(all_in.board.iter().map(|c| c.to_string()).collect::<Vec<_>>(), ["Ah", "Tc", "7d"]);
+        assert_eq!(all_in.equity.iter().map(|(p, _)| &data[*p]).collect::<Vec<_>>(), ["pondtree312", "Rifama"]);
+        assert!((all_in.equity[1].1 - 0.3535).abs() < 0.0001);
+
+        let ev = all_in.ev.iter().map(|(p, c)| (&data[*p], c.amount)).collect::<Vec<_>>();
+        assert_eq!(ev[2], ("spr1teg", 0.0));
+        assert!((ev[0].1 - (40.0 * (1.0 - 0.3535) - 20.0)).abs() < 0.01);
+        assert!((ev[1].1 - (40.0 * 0.3535 - 20.0)).abs() < 0.01);
+    }
+}
@@ -0,0 +1,542 @@
+//region Eval
+
+// Hand strength of 5 to 7 cards, the best five count:
+//
+// evaluate(&parse_cards("As Ks Qs Js Ts 2c 2d")).category() == Category::StraightFlush
+//
+// Ranks compare as integers, the category sits above five kicker ranks of 4 bits each, aces are 14.
+// Lows reverse the order of the same ranks, aces are 1 in A-5 lows where straights and flushes do not count.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+const CATEGORIES: [Category; 9] = [
+    Category::HighCard, Category::OnePair, Category::TwoPair, Category::ThreeOfAKind, Category::Straight,
+    Category::Flush, Category::FullHouse, Category::FourOfAKind, Category::StraightFlush,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank(pub u32);
+
+#[allow(dead_code)]
+impl HandRank {
+    fn new(category: Category, ranks: &[u8]) -> Self {
+        let kickers = (0..5).fold(0, |v, i| v << 4 | ranks.get(i).copied().unwrap_or(0) as u32);
+        HandRank((category as u32) << 20 | kickers)
+    }
+
+    pub fn category(&self) -> Category {
+        CATEGORIES[(self.0 >> 20) as usize]
+    }
+
+    // Ranks deciding ties within the category, highest first, 0 when unused
+    pub fn kickers(&self) -> [u8; 5] {
+        std::array::from_fn(|i| (self.0 >> (16 - 4 * i) & 0xf) as u8)
+    }
+}
+
+// Low hands, the greater rank is the better low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LowRank(pub HandRank);
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[allow(dead_code)]
+impl LowRank {
+    pub fn category(&self) -> Category {
+        self.0.category()
+    }
+
+    // Lo: 8,7,5,3,2
+    pub fn kickers(&self) -> [u8; 5] {
+        self.0.kickers()
+    }
+}
+
+fn rank(card: &Card) -> u8 {
+    if card.n == 1 { 14 } else { card.n }
+}
+
+// Highest card of a straight in a mask of rank bits, the ace also plays low in a wheel
+fn straight_high(mask: u16, wheel: bool) -> Option<u8> {
+    let m = if wheel { mask | (mask >> 14 & 1) << 1 } else { mask };
+    let runs = m & m >> 1 & m >> 2 & m >> 3 & m >> 4;
+    if runs == 0 { None } else { Some(15 - runs.leading_zeros() as u8 + 4) }
+}
+
+// Five highest ranks of a mask leaving out the used ones, 0 when there are fewer
+fn top_ranks(mask: u16, used: &[u8]) -> [u8; 5] {
+    let mut ranks = [0u8; 5];
+    let top = (2..=14u8).rev().filter(|r| mask & 1 << r != 0 && !used.contains(r));
+    ranks.iter_mut().zip(top).for_each(|(slot, r)| *slot = r);
+    ranks
+}
+
+pub fn evaluate(cards: &[Card]) -> HandRank {
+    evaluate_with(cards, true)
+}
+
+fn evaluate_with(cards: &[Card], wheel: bool) -> HandRank {
+    let mut suits = [0u16; 4];
+    let mut counts = [0u8; 15];
+    for c in cards {
+        suits[c.suit as usize] |= 1 << rank(c);
+        counts[rank(c) as usize] += 1;
+    }
+
+    if let Some(&flush) = suits.iter().find(|m| m.count_ones() >= 5) {
+        return match straight_high(flush, wheel) {
+            Some(high) => HandRank::new(Category::StraightFlush, &[high]),
+            None => HandRank::new(Category::Flush, &top_ranks(flush, &[])),
+        };
+    }
+
+    let (groups, n) = groups(&counts);
+    let groups = &groups[..n];
+    let all = suits.iter().fold(0, |m, s| m | s);
+    let kickers = |used: &[u8]| top_ranks(all, used);
+
+    match groups {
+        [(4, q), ..] => HandRank::new(Category::FourOfAKind, &[*q, kickers(&[*q])[0]]),
+        [(3, t), (c, p), ..] if *c >= 2 => HandRank::new(Category::FullHouse, &[*t, *p]),
+        _ => match straight_high(all, wheel) {
+            Some(high) => HandRank::new(Category::Straight, &[high]),
+            None => match groups {
+                [(3, t), ..] => {
+                    let k = kickers(&[*t]);
+                    HandRank::new(Category::ThreeOfAKind, &[*t, k[0], k[1]])
+                }
+                [(2, a), (2, b), ..] => HandRank::new(Category::TwoPair, &[*a, *b, kickers(&[*a, *b])[0]]),
+                [(2, p), ..] => {
+                    let k = kickers(&[*p]);
+                    HandRank::new(Category::OnePair, &[*p, k[0], k[1], k[2]])
+                }
+                _ => HandRank::new(Category::HighCard, &kickers(&[])),
+            },
+        },
+    }
+}
+
+// Ranks by count then rank, quads first, and how many there are
+fn groups(counts: &[u8; 15]) -> ([(u8, u8); 14], usize) {
+    let mut groups = [(0u8, 0u8); 14];
+    let mut n = 0;
+    for count in (1..=4u8).rev() {
+        for r in (1..=14u8).rev().filter(|r| counts[*r as usize] == count) {
+            groups[n] = (count, r);
+            n += 1;
+        }
+    }
+    (groups, n)
+}
+
+// Five cards by their pairs only, aces are 1
+fn evaluate_a5(cards: &[Card]) -> HandRank {
+    let mut counts = [0u8; 15];
+    cards.iter().for_each(|c| counts[c.n as usize] += 1);
+    let (groups, n) = groups(&counts);
+    let groups = &groups[..n];
+    let category = match groups {
+        [(4, _), ..] => Category::FourOfAKind,
+        [(3, _), (2, _)] => Category::FullHouse,
+        [(3, _), ..] => Category::ThreeOfAKind,
+        [(2, _), (2, _), ..] => Category::TwoPair,
+        [(2, _), ..] => Category::OnePair,
+        _ => Category::HighCard,
+    };
+    let mut ranks = [0u8; 5];
+    ranks.iter_mut().zip(groups).for_each(|(slot, (_, r))| *slot = *r);
+    HandRank::new(category, &ranks)
+}
+
+// Best A-5 low of 5 to 7 cards
+pub fn evaluate_low(cards: &[Card]) -> LowRank {
+    let n = cards.len();
+    (0u32..1 << n).filter(|m| m.count_ones() == 5)
+        .map(|m| {
+            let mut five = [cards[0]; 5];
+            let picked = cards.iter().enumerate().filter(|(i, _)| m & 1 << i != 0);
+            five.iter_mut().zip(picked).for_each(|(slot, (_, c))| *slot = *c);
+            LowRank(evaluate_a5(&five))
+        })
+        .max()
+        .unwrap_or(LowRank(evaluate_a5(cards)))
+}
+
+//endregion
+
+//region Eval - Games
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Game {
+    Holdem,
+    Omaha,
+    // Eight or better for the low half
+    OmahaHiLo,
+    Razz,
+    // 2-7 lowball, aces are high, straights and flushes count
+    Lowball27,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Strength {
+    pub high: Option<HandRank>,
+    pub low: Option<LowRank>,
+}
+
+#[allow(dead_code)]
+impl Game {
+    // Hold'em No Limit, Omaha Hi/Lo Pot Limit, Razz Limit, Triple Draw 2-7 Lowball Limit
+    pub fn from_game_type(game_type: &str) -> Option<Self> {
+        let g = game_type.to_lowercase();
+        if g.contains("omaha") {
+            Some(if g.contains("hi/lo") { Game::OmahaHiLo } else { Game::Omaha })
+        } else if g.contains("razz") {
+            Some(Game::Razz)
+        } else if g.contains("2-7") {
+            Some(Game::Lowball27)
+        } else if g.contains("hold'em") || g.contains("holdem") {
+            Some(Game::Holdem)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_board(&self) -> bool {
+        matches!(self, Game::Holdem | Game::Omaha | Game::OmahaHiLo)
+    }
+
+    pub fn evaluate(&self, hole: &[Card], board: &[Card]) -> Strength {
+        match self {
+            Game::Holdem => Strength{high: Some(with_board(hole, board, evaluate)), low: None},
+            Game::Omaha => Strength{high: omaha(hole, board, |five| Some(evaluate(five))), low: None},
+            Game::OmahaHiLo => Strength{
+                high: omaha(hole, board, |five| Some(evaluate(five))),
+                low: omaha(hole, board, |five| Some(LowRank(evaluate_a5(five))).filter(|l| l.category() == Category::HighCard && l.kickers()[0] <= 8)),
+            },
+            Game::Razz => Strength{high: None, low: Some(with_board(hole, board, evaluate_low))},
+            Game::Lowball27 => Strength{high: None, low: Some(LowRank(evaluate_with(hole, false)))},
+        }
+    }
+}
+
+// Hole cards and board side by side, the first seven count
+fn with_board<R>(hole: &[Card], board: &[Card], rank: impl Fn(&[Card]) -> R) -> R {
+    let mut cards = [Card{n: 0, suit: Suit::Spade}; 7];
+    let n = (hole.len() + board.len()).min(7);
+    cards.iter_mut().zip(hole.iter().chain(board)).for_each(|(slot, c)| *slot = *c);
+    rank(&cards[..n])
+}
+
+// Exactly two hole cards and three of the board
+fn omaha<R: Ord>(hole: &[Card], board: &[Card], rank: impl Fn(&[Card]) -> Option<R>) -> Option<R> {
+    let (h, n) = (hole.len(), board.len());
+    let mut best = None;
+    for a in 0..h {
+        for b in a + 1..h {
+            for x in 0..n {
+                for y in x + 1..n {
+                    for z in y + 1..n {
+                        best = best.max(rank(&[hole[a], hole[b], board[x], board[y], board[z]]));
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+//endregion
+
+//region Eval - Showdown
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    High(HandRank),
+    Low(LowRank),
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct PotShowdown {
+    pub pot: Pot,
+    // The half of a split hi/lo pot, None when one hand takes the pot
+    pub hi_lo: Option<HiLo>,
+    // Best shown hands among the players eligible for the pot
+    pub winners: Vec<Span>,
+    pub rank: Rank,
+    // Players the log says collected the pot
+    pub collected: Vec<Span>,
+}
+
+#[allow(dead_code)]
+impl PotShowdown {
+    pub fn agrees(&self, data: &str) -> bool {
+        let names = |spans: &[Span]| {
+            let mut names = spans.iter().map(|s| &data[*s]).collect::<Vec<_>>();
+            names.sort_unstable();
+            names.dedup();
+            names
+        };
+        names(&self.winners) == names(&self.collected)
+    }
+}
+
+#[allow(dead_code)]
+impl Hand {
+    pub fn game(&self, data: &str) -> Option<Game> {
+        Game::from_game_type(&data[self.header.info.game_type])
+    }
+
+    // The board of the hand, the summary lists it and the last board street deals it
+    pub fn board(&self) -> Option<Cards> {
+        self.summary.boards.first().cloned().or_else(|| {
+            let street = self.streets.iter().rev().find(|s| s.cards.is_some())?;
+            let mut cards = street.cards.clone()?;
+            cards.extend(street.new_card);
+            Some(cards)
+        })
+    }
+
+    // Winners of every pot contested at showdown by the cards, hands run more than once are left out
+    pub fn showdown(&self, data: &str) -> Vec<PotShowdown> {
+        let Some(game) = self.game(data) else { return Vec::new() };
+        let board = self.board().unwrap_or_default();
+        if self.summary.boards.len() > 1 || game.has_board() && board.len() != 5 {
+            return Vec::new();
+        }
+
+        let known = self.known_hole_cards(data);
+        let shown = self.actions().filter_map(|a| match a {
+            Action::ShowHand(name, _) => Some(&data[*name]),
+            _ => None,
+        }).chain(self.summary.seats.iter().filter(|s| s.cards.is_some() && !s.mucked).map(|s| &data[s.name])).collect::<Vec<_>>();
+
+        // The half of a hi/lo pot a player collected is on the CollectedPot action
+        let half = |name: &Span, amount: &Currency| self.actions().find_map(|a| match a {
+            Action::CollectedPot(_, n, a, hi_lo) if n == name && a.amount == amount.amount => Some(*hi_lo),
+            _ => None,
+        }).flatten();
+
+        let mut showdowns = Vec::new();
+        for layer in self.pots(data) {
+            let strengths = layer.eligible.iter().filter(|name| shown.contains(&&data[**name])).filter_map(|name| {
+                let hole = known.get(&data[*name])?;
+                Some((*name, game.evaluate(hole, &board)))
+            }).collect::<Vec<_>>();
+            let high = strengths.iter().filter_map(|(name, s)| Some((*name, Rank::High(s.high?)))).collect::<Vec<_>>();
+            let low = strengths.iter().filter_map(|(name, s)| Some((*name, Rank::Low(s.low?)))).collect::<Vec<_>>();
+
+            // A hi/lo pot without a qualifying low goes to the high hand
+            let split = !high.is_empty() && !low.is_empty();
+            for (ranks, hi_lo) in [(high, HiLo::High), (low, HiLo::Low)] {
+                let Some(rank) = ranks.iter().map(|(_, r)| *r).max() else { continue };
+                let hi_lo = Some(hi_lo).filter(|_| split);
+                showdowns.push(PotShowdown{
+                    pot: layer.pot,
+                    hi_lo,
+                    winners: ranks.iter().filter(|(_, r)| *r == rank).map(|(name, _)| *name).collect(),
+                    rank,
+                    collected: layer.collected.iter().filter(|(name, amount)| hi_lo.is_none() || half(name, amount) == hi_lo).map(|(name, _)| *name).collect(),
+                });
+            }
+        }
+        showdowns
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories() {
+        let eval = |s: &str| evaluate(&parse_cards(s));
+        assert_eq!(eval("As Ks Qs Js Ts 2c 2d").category(), Category::StraightFlush);
+        assert_eq!(eval("5d 4d 3d 2d Ad Kd").kickers(), [5, 0, 0, 0, 0]);
+        assert_eq!(eval("9c 9d 9h 9s 2c Kd 3h").kickers(), [9, 13, 0, 0, 0]);
+        assert_eq!(eval("9c 9d 9h 2s 2c Kd Kh").kickers(), [9, 13, 0, 0, 0]);
+        assert_eq!(eval("9c 9d 9h 2s 2c 2d Kh").category(), Category::FullHouse);
+        assert_eq!(eval("Ah 9h 7h 4h 2h Kd").category(), Category::Flush);
+        assert_eq!(eval("Ah 2c 3d 4s 5h 9d").kickers(), [5, 0, 0, 0, 0]);
+        assert_eq!(eval("Ah Kc Qd Js Th").category(), Category::Straight);
+        assert_eq!(eval("7c 7d 7h Kd 2s 3c").kickers(), [7, 13, 3, 0, 0]);
+        assert_eq!(eval("7c 7d 5h 5d 3s 3c Ah").kickers(), [7, 5, 14, 0, 0]);
+        assert_eq!(eval("7c 7d Ah Kd 2s 3c").kickers(), [7, 14, 13, 3, 0]);
+        assert_eq!(eval("7c 9d Ah Kd 2s 3c 4d").kickers(), [14, 13, 9, 7, 4]);
+
+        assert!(eval("Ah Kc Qd Js Th") > eval("Ah Ac Ad Ks Qh"));
+        assert!(eval("Ah Ac 9d 8s 2h") > eval("Kh Kc Qd Js Th 4h"));
+        assert!(eval("Ah Ac 9d 8s 3h") > eval("Ad As 9c 8h 2d"));
+        assert_eq!(eval("Ah Ac 9d 8s 3h"), eval("Ad As 9c 8h 3d"));
+    }
+
+    #[test]
+    fn game_evaluators() {
+        let cards = |s: &str| parse_cards(s);
+        assert_eq!(Game::from_game_type("Omaha Hi/Lo Pot Limit"), Some(Game::OmahaHiLo));
+        assert_eq!(Game::from_game_type("Triple Draw 2-7 Lowball Limit"), Some(Game::Lowball27));
+        assert_eq!(Game::from_game_type("7 Card Stud Limit"), None);
+
+        // Four hearts on the board make no flush with a single heart
+        let omaha = Game::Omaha.evaluate(&cards("Ah Kc Kd 2s"), &cards("Qh Jh 9h 4h 3c"));
+        assert_eq!(omaha.high.unwrap().category(), Category::OnePair);
+        let hi_lo = Game::OmahaHiLo.evaluate(&cards("Ah 2c 9d 9c"), &cards("8d 3c Kd 9s 6d"));
+        assert_eq!(hi_lo.high.unwrap().category(), Category::ThreeOfAKind);
+        assert_eq!(hi_lo.low.unwrap().kickers(), [8, 6, 3, 2, 1]);
+        assert!(Game::OmahaHiLo.evaluate(&cards("Ah 9c 9d Kc"), &cards("8d 3c Kd 2s 6d")).low.is_none());
+
+        let razz = |s: &str| Game::Razz.evaluate(&cards(s), &[]).low.unwrap();
+        assert_eq!(razz("Kd Kh 6h 5c 6s Qd Jd").kickers(), [13, 12, 11, 6, 5]);
+        assert_eq!(razz("Kd Kh Kc 6s 6d 6h 2c").category(), Category::TwoPair);
+        assert!(razz("5c 4h 3d 2s Ac Kd Qh") > razz("6c 4h 3d 2s Ac Kd Qh"));
+
+        let deuce = |s: &str| Game::Lowball27.evaluate(&cards(s), &[]).low.unwrap();
+        assert_eq!(deuce("7h 5c 4d 3s 2h").kickers(), [7, 5, 4, 3, 2]);
+        assert!(deuce("7h 5c 4d 3s 2h") > deuce("Ah 5c 4d 3s 2h"));
+        assert!(deuce("8h 6c 4d 3s 2h") > deuce("6h 5c 4d 3s 2h"));
+        assert!(deuce("Kh Qc Jd 9s 8h") > deuce("7h 5h 4h 3h 2h"));
+    }
+
+    #[test]
+    fn stud_draw_and_hi_lo_showdowns() {
+        let data = "PokerStars Hand #208966141602:  HORSE (Razz Limit, $0.04/$0.08 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 8-max
+Seat 1: pondtree312 ($2 in chips)
+Seat 2: Rifama ($2 in chips)
+pondtree312: posts the ante $0.01
+Rifama: posts the ante $0.01
+*** 3rd STREET ***
+Dealt to pondtree312 [Kh]
+Dealt to Rifama [9s 4d 2c]
+pondtree312: brings in for $0.02
+Rifama: completes it to $0.04
+pondtree312: calls $0.02
+*** 4th STREET ***
+Dealt to pondtree312 [Kh] [5c]
+Dealt to Rifama [9s 4d 2c] [7h]
+Rifama: bets $0.04
+pondtree312: calls $0.04
+*** 7th STREET ***
+Dealt to Rifama [9s 4d 2c 7h 5d Ah] [3s]
+Rifama: checks
+pondtree312: checks
+*** SHOW DOWN ***
+Rifama: shows [9s 4d 2c 7h 5d Ah 3s] (Lo: 5,4,3,2,A)
+pondtree312: shows [Qd Jd Kh 5c 6h 6s Kd] (Lo: K,Q,J,6,5)
+Rifama collected $0.18 from pot
+*** SUMMARY ***
+Total pot $0.18 | Rake $0
+Seat 1: pondtree312 showed [Qd Jd Kh 5c 6h 6s Kd] and lost with Lo: K,Q,J,6,5
+Seat 2: Rifama showed [9s 4d 2c 7h 5d Ah 3s] and won ($0.18) with Lo: 5,4,3,2,A
+";
+        let hands = parse_string(data);
+        let showdowns = hands[0].showdown(data);
+        assert_eq!(showdowns.len(), 1);
+        assert!(showdowns[0].agrees(data));
+        assert_eq!(showdowns[0].hi_lo, None);
+        assert!(matches!(showdowns[0].rank, Rank::Low(low) if low.kickers() == [5, 4, 3, 2, 1]));
+
+        let data = "PokerStars Hand #208966141597:  Triple Draw 2-7 Lowball Limit ($0.10/$0.20 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($4 in chips)
+Seat 2: Rifama ($4 in chips)
+Rifama: posts small blind $0.05
+pondtree312: posts big blind $0.10
+*** DEALING HANDS ***
+Dealt to Rifama [7h 2c 8d 5s Kc]
+Rifama: calls $0.05
+pondtree312: checks
+*** FIRST DRAW ***
+Rifama: discards 1 card [Kc]
+Dealt to Rifama [7h 2c 8d 5s] [3d]
+pondtree312: discards 2 cards
+Rifama: checks
+pondtree312: checks
+*** SHOW DOWN ***
+Rifama: shows [7h 2c 8d 5s 3d] (Lo: 8,7,5,3,2)
+Rifama collected $0.19 from pot
+*** SUMMARY ***
+Total pot $0.20 | Rake $0.01
+Seat 1: pondtree312 (button) showed [9h 8c 6d 4s 2h] and lost with Lo: 9,8,6,4,2
+Seat 2: Rifama (small blind) showed [7h 2c 8d 5s 3d] and won ($0.19) with Lo: 8,7,5,3,2
+";
+        let hands = parse_string(data);
+        let showdowns = hands[0].showdown(data);
+        assert_eq!(showdowns.len(), 1);
+        assert_eq!(&data[showdowns[0].winners[0]], "Rifama");
+        assert!(showdowns[0].agrees(data));
+
+        let data = "PokerStars Hand #208966141599:  Omaha Hi/Lo Pot Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+Rifama: posts small blind $0.25
+spr1teg: posts big blind $0.50
+*** HOLE CARDS ***
+pondtree312: calls $0.50
+Rifama: calls $0.25
+spr1teg: checks
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+Rifama: shows [Ah 2c 5d Kc] (HI: a pair of Aces; LO: 6,5,3,2,A)
+spr1teg: shows [9h 9d Qs Js] (HI: three of a kind, Nines)
+pondtree312: mucks hand
+spr1teg collected $0.72 from pot
+Rifama collected $0.71 from pot
+*** SUMMARY ***
+Total pot $1.50 | Rake $0.07
+Board [8d 3c Ad 9c 6d]
+Seat 1: pondtree312 (button) mucked [Kh Kd Qh Jc]
+Seat 2: Rifama (small blind) showed [Ah 2c 5d Kc] and won ($0.71) with LO: 6,5,3,2,A
+Seat 3: spr1teg (big blind) showed [9h 9d Qs Js] and won ($0.72) with HI: three of a kind, Nines
+";
+        let hands = parse_string(data);
+        let showdowns = hands[0].showdown(data);
+        assert_eq!(showdowns.iter().map(|s| (s.hi_lo, &data[s.winners[0]])).collect::<Vec<_>>(),
+            [(Some(HiLo::High), "spr1teg"), (Some(HiLo::Low), "Rifama")]);
+        assert!(showdowns.iter().all(|s| s.agrees(data)));
+    }
+
+    #[test]
+    fn showdowns_agree_with_the_log() {
+        for file in ["data/example/pokerstars_example.txt", "data/example/pluribus_example.txt"] {
+            let data = std::fs::read_to_string(file).unwrap();
+            let data = data.as_str();
+            let hands = parse_string(data);
+            let showdowns = hands.iter().flat_map(|h| h.showdown(data).into_iter().map(move |s| (h, s))).collect::<Vec<_>>();
+            assert!(!showdowns.is_empty());
+            for (hand, s) in showdowns {
+                assert!(s.agrees(data), "Hand #{} {:?}", &data[hand.header.info.id], s);
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//region GGPoker
+
+// GGPoker and Natural8 write the PokerStars grammar with a few additions:
+//
+// Poker Hand #HD1234567: Hold'em No Limit ($0.02/$0.05) - 2021/01/01 12:00:00
+// Dealt to 7a8b9c                       hidden hole cards of opponents
+// *** FIRST FLOP *** [8d 3c Ad]         run it multiple times
+// toyochan: Pays Cashout Risk ($0.50)
+// toyochan: Chooses to EV Cashout
+// toyochan: Receives Cashout ($1.20)
+// Total pot $2.32 | Rake $0.08 | Jackpot $0 | Bingo $0 | Fortune $0 | Tax $0
+// Hand was run two times
+
+use super::*;
+
+pub struct GGPoker;
+
+impl SiteParser for GGPoker {
+    fn name(&self) -> &'static str { "GGPoker" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        let mut parser = Parser::new(data);
+        parser.site_name = "GGPoker";
+        parser.site_actions = Some(parse_action);
+        parser.parse();
+        parser.hands
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("Poker Hand #")
+}
+
+fn parse_action(p: &Parser) -> Option<Action> {
+    let line = p.line();
+    let r = if line.ends_with(": Chooses to EV Cashout")
+    {
+        Action::Event(Event::EvCashOut, p.span(line.prefix(':')))
+    }
+    else if line.contains(": Pays Cashout Risk (")
+    {
+        Action::PaysCashOutRisk(p.span(line.prefix(':')), parse_currency(line.rbetween('(', ')')))
+    }
+    else if line.contains(": Receives Cashout (")
+    {
+        let amount = parse_currency(line.rbetween('(', ')'));
+        Action::CashOut(p.span(line.prefix(':')), amount, Currency{symbol: amount.symbol, amount: 0.0})
+    }
+    else {
+        return None;
+    };
+    Some(r)
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_it_twice_and_cashout() {
+        let data = "Poker Hand #HD1234567: Hold'em No Limit ($0.02/$0.05) - 2021/01/01 12:00:00
+Table 'NLHWhite25' 6-max Seat #1 is the button
+Seat 1: 7a8b9c ($5 in chips)
+Seat 2: Hero ($5 in chips)
+Hero: posts small blind $0.02
+7a8b9c: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to 7a8b9c 
+Dealt to Hero [Ah Kd]
+Hero: raises $4.95 to $5 and is all-in
+7a8b9c: calls $4.95 and is all-in
+Hero: shows [Ah Kd]
+7a8b9c: shows [Jc Jd]
+7a8b9c: Chooses to EV Cashout
+Hero: Pays Cashout Risk ($2.10)
+*** FIRST FLOP *** [8d 3c Ad]
+*** SECOND FLOP *** [Jh 2s 4c]
+*** FIRST TURN *** [8d 3c Ad] [9c]
+*** SECOND TURN *** [Jh 2s 4c] [Qd]
+*** FIRST RIVER *** [8d 3c Ad 9c] [6d]
+*** SECOND RIVER *** [Jh 2s 4c Qd] [5h]
+*** FIRST SHOWDOWN ***
+Hero collected $4.75 from pot
+*** SECOND SHOWDOWN ***
+7a8b9c collected $4.75 from pot
+7a8b9c: Receives Cashout ($4.61)
+*** SUMMARY ***
+Total pot $10 | Rake $0.50 | Jackpot $0.10 | Bingo $0 | Fortune $0 | Tax $0.02
+Hand was run two times
+FIRST Board [8d 3c Ad 9c 6d]
+SECOND Board [Jh 2s 4c Qd 5h]
+Seat 1: 7a8b9c (big blind) showed [Jc Jd] and won ($4.75) with three of a kind, Jacks
+Seat 2: Hero (small blind) showed [Ah Kd] and won ($4.75) with a pair of Aces
+";
+        assert!(detect_site(data).is_some_and(|site| site.name() == "GGPoker"));
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.hero().unwrap()], "Hero");
+        assert_eq!(hand.hole_cards.dealt_to.len(), 1);
+        assert!(matches!(hand.hole_cards.actions[4], Action::Event(Event::EvCashOut, _)));
+        assert!(matches!(hand.hole_cards.actions[5], Action::PaysCashOutRisk(_, Currency{amount, ..}) if amount == 2.10));
+        assert_eq!(hand.streets.len(), 8);
+        assert_eq!(hand.streets[1].index, 1);
+        assert!(matches!(hand.streets[7].actions[1], Action::CashOut(_, Currency{amount, ..}, _) if amount == 4.61));
+
+        let summary = &hand.summary;
+        assert_eq!(summary.rake.unwrap().amount, 0.5);
+        assert_eq!(summary.jackpot.unwrap().amount, 0.1);
+        assert_eq!(summary.fees.len(), 3);
+        assert_eq!(&data[summary.fees[2].0], "Tax");
+        assert_eq!(summary.boards.len(), 2);
+        assert_eq!(summary.seats.len(), 2);
+    }
+}
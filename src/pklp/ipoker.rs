@@ -0,0 +1,327 @@
+//region iPoker
+
+// <session sessioncode="-1">
+//   <general><nickname>spr1teg</nickname><gametype>Holdem NL €0.25/€0.50</gametype><tablename>Acamar</tablename>...</general>
+//   <game gamecode="5551234567">
+//     <general><players><player seat="3" name="spr1teg" chips="€50.50" dealer="1" win="€8.85" bet="€4.25"/></players></general>
+//     <round no="0"><action no="1" player="pondtree312" sum="€0.25" type="1"/></round>
+//     <round no="1"><cards type="Pocket" player="spr1teg">S8 DJ</cards><action no="3" player="spr1teg" sum="€1.25" type="23"/></round>
+//     <round no="2"><cards type="Flop" player="">D8 C3 DA</cards>...</round>
+//   </game>
+// </session>
+
+use super::*;
+
+pub struct IPoker;
+
+impl SiteParser for IPoker {
+    fn name(&self) -> &'static str { "iPoker" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.contains("<session") && head.contains("<game")
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        parse_session(data)
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        xml_tags(data).filter(|t| t.name == "game" && !t.close).count()
+    }
+}
+
+//region XML
+
+struct XmlTag<'a> {
+    name: &'a str,
+    attrs: &'a str,
+    text: &'a str,
+    close: bool,
+}
+
+impl<'a> XmlTag<'a> {
+    fn attr(&self, key: &str) -> Option<&'a str> {
+        let mut parts = self.attrs.split('"');
+        while let (Some(k), Some(v)) = (parts.next(), parts.next()) {
+            if k.trim().trim_end_matches('=').trim_end() == key {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+
+fn xml_tags(data: &str) -> impl Iterator<Item = XmlTag<'_>> {
+    let mut rest = data;
+    std::iter::from_fn(move || loop {
+        let start = rest.find('<')?;
+        let end = start + rest[start..].find('>')?;
+        let inner = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        if inner.starts_with(['?', '!']) {
+            continue;
+        }
+        let text = rest[..rest.find('<').unwrap_or(rest.len())].trim();
+        let (close, inner) = match inner.strip_prefix('/') {
+            Some(inner) => (true, inner),
+            None => (false, inner.trim_end_matches('/')),
+        };
+        let (name, attrs) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+        return Some(XmlTag{name, attrs, text, close});
+    })
+}
+
+//endregion
+
+#[derive(Default)]
+struct Session<'a> {
+    // iPoker does not write its name, the session element is what tells its files apart
+    site: Option<&'a str>,
+    nickname: &'a str,
+    game_type: &'a str,
+    table_name: &'a str,
+    max_players: u8,
+    small_blind: Option<Currency>,
+    big_blind: Option<Currency>,
+    symbol: Option<char>,
+    play_money: bool,
+}
+
+// Winnings and revealed hands are only stored with the players
+struct Game<'a> {
+    header: Header,
+    b: HandBuilder<'a>,
+    shown: Vec<(&'a str, Cards)>,
+    won: Vec<(&'a str, Currency)>,
+    rake: Float,
+}
+
+fn parse_amount(value: &str, symbol: Option<char>) -> Option<Currency> {
+    // €0.25, 0.25, 0,25
+    let c = if value.contains(',') && !value.contains('.') {
+        parse_currency_any(&value.replace(',', "."))
+    } else {
+        parse_currency_any(&value.replace(',', ""))
+    }?;
+    Some(match (c.symbol, symbol) {
+        (NO_CURRENCY, Some(symbol)) => Currency{symbol, amount: c.amount},
+        _ => c,
+    })
+}
+
+fn parse_ipoker_cards(s: &str) -> Cards {
+    // Suit first, "X" for hidden cards: "S8 DJ", "H10 CA", "X X"
+    let mut cards = Cards::new();
+    for c in s.split_whitespace().filter(|c| c.len() > 1 && c.is_char_boundary(1)) {
+        let (suit, rank) = c.split_at(1);
+        let rank = if rank == "10" { "T" } else { rank };
+        if "SHDC".contains(suit) && rank.len() == 1 && "23456789TJQKA".contains(rank) {
+            cards.extend(parse_cards(&format!("{}{}", rank, suit.to_lowercase())));
+        }
+    }
+    cards
+}
+
+fn parse_session(data: &str) -> Vec<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut hands = Vec::new();
+    let mut session = Session::default();
+    let mut game: Option<Game> = None;
+
+    for tag in xml_tags(data) {
+        if tag.close {
+            if tag.name == "game" {
+                if let Some(game) = game.take() {
+                    hands.push(finish_game(game, data));
+                }
+            }
+            continue;
+        }
+        let Some(Game{header, b, shown, won, rake}) = game.as_mut() else {
+            match tag.name {
+                "session" => session.site = Some(tag.name),
+                "nickname" => session.nickname = tag.text,
+                "gametype" => session.game_type = tag.text,
+                "tablename" => session.table_name = tag.text,
+                "tablesize" | "maxplayers" => session.max_players = parse_integer(tag.text).map_or(0, |(v, _)| v as u8),
+                "smallblind" => session.small_blind = parse_amount(tag.text, session.symbol),
+                "bigblind" => session.big_blind = parse_amount(tag.text, session.symbol),
+                "mode" => session.play_money = tag.text.contains("play"),
+                "currency" => session.symbol = match tag.text {
+                    "EUR" => Some('\u{20ac}'),
+                    "USD" => Some('$'),
+                    "GBP" => Some('£'),
+                    _ => None,
+                },
+                "game" => game = Some(Game{header: new_header(&tag, &session, data), b: HandBuilder::default(), shown: Vec::new(), won: Vec::new(), rake: 0.0}),
+                _ => {}
+            }
+            continue;
+        };
+
+        match tag.name {
+            "player" => {
+                let (Some(name), Some(seat)) = (tag.attr("name"), tag.attr("seat").and_then(parse_integer)) else { continue };
+                let chips = tag.attr("chips").and_then(|c| parse_amount(c, session.symbol)).unwrap_or(Currency{symbol: NO_CURRENCY, amount: 0.0});
+                if tag.attr("dealer") == Some("1") { header.table.button = seat.0 as u8; }
+                if let Some(win) = tag.attr("win").and_then(|w| parse_amount(w, session.symbol)).filter(|w| w.amount > 0.0) {
+                    won.push((name, win));
+                }
+                *rake += tag.attr("rakeamount").and_then(|r| parse_amount(r, session.symbol)).map_or(0.0, |r| r.amount);
+                header.players.push(Player{name: span(name), seat: seat.0 as u8, chips, bounty: None});
+            }
+            "round" if tag.attr("no") == Some("1") => { b.hole_cards(); }
+            "cards" => {
+                let cards = parse_ipoker_cards(tag.text);
+                match (tag.attr("type"), tag.attr("player")) {
+                    (Some("Pocket"), Some(name)) if name == session.nickname => b.hole_cards().dealt_to.push((span(name), cards)),
+                    (Some("Pocket"), Some(name)) if !cards.is_empty() => shown.push((name, cards)),
+                    (Some("Flop"), _) => b.deal_board(StreetType::Flop, cards),
+                    (Some("Turn"), _) => b.deal_board(StreetType::Turn, cards),
+                    (Some("River"), _) => b.deal_board(StreetType::River, cards),
+                    _ => {}
+                }
+            }
+            "action" => {
+                let (Some(name), Some(t)) = (tag.attr("player"), tag.attr("type")) else { continue };
+                let name_span = span(name);
+                let sum = tag.attr("sum").and_then(|s| parse_amount(s, session.symbol)).unwrap_or(Currency{symbol: NO_CURRENCY, amount: 0.0});
+                match t {
+                    "0" => b.push(Action::Fold(name_span, None)),
+                    "1" => b.blind(name, name_span, BlindType::Small, sum),
+                    "2" => b.blind(name, name_span, BlindType::Big, sum),
+                    "4" => b.push(Action::Play(Play::Check, name_span, None)),
+                    "3" | "5" | "7" | "23" => b.play(name, name_span, sum, t == "7"),
+                    "8" => b.push(Action::Event(Event::Sitout, name_span)),
+                    "15" => b.blind(name, name_span, BlindType::Ante, sum),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    hands
+}
+
+fn new_header(game: &XmlTag, session: &Session, data: &str) -> Header {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    // Holdem NL €0.25/€0.50
+    let (game_type, stakes) = match session.game_type.rsplit_once(' ') {
+        Some((game_type, stakes)) if stakes.contains('/') => (game_type, parse_stakes(stakes)),
+        _ => (session.game_type, None),
+    };
+    let zero = Currency{symbol: session.symbol.unwrap_or(NO_CURRENCY), amount: 0.0};
+    let (buy_in_min, buy_in_max) = match (session.small_blind, session.big_blind, stakes) {
+        (Some(sb), Some(bb), _) => (sb, bb),
+        (_, _, Some((sb, bb))) => (sb, bb),
+        _ => (zero, zero),
+    };
+    let id = game.attr("gamecode").unwrap_or(&game.name[..0]);
+    let info = HandInfo{
+        site_name: "iPoker", site: span(session.site.unwrap_or(game.name)), hand: span(game.name), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(game_type), buy_in_min, buy_in_max,
+    };
+    let table = Table{name: span(session.table_name), max_players: session.max_players, button: 0, play_money: session.play_money};
+    Header{info, table, players: PlayerVec::new(), actions: ActionVec::new()}
+}
+
+fn finish_game(game: Game, data: &str) -> Hand {
+    let Game{header, mut b, shown, won, rake} = game;
+    if !shown.is_empty() {
+        b.showdown();
+    }
+    for (name, cards) in shown {
+        b.push(Action::ShowHand(Span::from_str_slice(name, data), cards));
+    }
+    for (name, amount) in won {
+        b.push(Action::CollectedPot(Pot::Main, Span::from_str_slice(name, data), amount, None));
+    }
+    let symbol = header.info.buy_in_max.symbol;
+    b.finish(header, (rake > 0.0).then_some(Currency{symbol, amount: rake}), data)
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ipoker_session() {
+        let data = r#"<?xml version="1.0" encoding="utf-8"?>
+<session sessioncode="-1">
+  <general>
+    <nickname>spr1teg</nickname>
+    <gametype>Holdem NL €0.25/€0.50</gametype>
+    <tablename>Acamar</tablename>
+    <tablesize>6</tablesize>
+    <smallblind>€0.25</smallblind>
+    <bigblind>€0.50</bigblind>
+    <currency>EUR</currency>
+    <mode>real</mode>
+  </general>
+  <game gamecode="5551234567">
+    <general>
+      <startdate>2020-02-08 04:01:19</startdate>
+      <players>
+        <player seat="1" name="pondtree312" chips="€49.50" dealer="0" win="€0" bet="€4.25"/>
+        <player seat="2" name="Rifama" chips="€50" dealer="0" win="€0" bet="€0.50"/>
+        <player seat="3" name="spr1teg" chips="€50.50" dealer="1" win="€8.85" bet="€4.25" rakeamount="€0.15"/>
+      </players>
+    </general>
+    <round no="0">
+      <action no="1" player="pondtree312" sum="€0.25" type="1"/>
+      <action no="2" player="Rifama" sum="€0.50" type="2"/>
+    </round>
+    <round no="1">
+      <cards type="Pocket" player="pondtree312">D5 SK</cards>
+      <cards type="Pocket" player="Rifama">X X</cards>
+      <cards type="Pocket" player="spr1teg">S8 DJ</cards>
+      <action no="3" player="spr1teg" sum="€1.25" type="23"/>
+      <action no="4" player="pondtree312" sum="€1" type="3"/>
+      <action no="5" player="Rifama" sum="€0" type="0"/>
+    </round>
+    <round no="2">
+      <cards type="Flop" player="">D8 C3 DA</cards>
+      <action no="6" player="pondtree312" sum="€0" type="4"/>
+      <action no="7" player="spr1teg" sum="€1" type="5"/>
+      <action no="8" player="pondtree312" sum="€3" type="23"/>
+      <action no="9" player="spr1teg" sum="€2" type="3"/>
+    </round>
+    <round no="3">
+      <cards type="Turn" player="">C9</cards>
+    </round>
+    <round no="4">
+      <cards type="River" player="">H10</cards>
+    </round>
+  </game>
+</session>
+"#;
+        assert!(detect_site(data).is_some_and(|site| site.name() == "iPoker"));
+        assert_eq!(IPoker.count_hands(data), 1);
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.info.site], "session");
+        assert_eq!(&data[hand.header.info.id], "5551234567");
+        assert_eq!(&data[hand.header.info.game_type], "Holdem NL");
+        assert_eq!(hand.header.info.buy_in_max.amount, 0.5);
+        assert_eq!(&data[hand.header.table.name], "Acamar");
+        assert_eq!(hand.header.table.button, 3);
+        assert_eq!(hand.header.players.len(), 3);
+        assert_eq!(hand.header.actions.len(), 2);
+        assert_eq!(&data[hand.hero().unwrap()], "spr1teg");
+        assert_eq!(hand.hole_cards.dealt_to[0].1.len(), 2);
+
+        assert!(matches!(hand.hole_cards.actions[0], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, ..}), ..})) if amount == 1.25));
+        assert_eq!(hand.streets.len(), 4);
+        assert!(matches!(hand.streets[0].actions[2], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, ..}), ..})) if amount == 3.0));
+        assert_eq!(hand.streets[2].new_card.unwrap().n, 10);
+        assert!(matches!(hand.streets[3].actions[0], Action::ShowHand(..)));
+        assert!(matches!(hand.streets[3].actions[1], Action::CollectedPot(Pot::Main, _, Currency{amount, symbol: '€'}, None) if amount == 8.85));
+        assert_eq!(hand.summary.rake.unwrap().amount, 0.15);
+        assert_eq!(hand.summary.seats.len(), 2);
+    }
+}
@@ -0,0 +1,421 @@
+//region OHH
+
+// Open Hand History JSON (https://hh-specs.handhistory.org)
+//
+// {"ohh": {"spec_version": "1.4.6", "site_name": "PokerStars", "game_number": "206310212744", ...,
+//   "players": [{"id": 0, "seat": 1, "name": "pondtree312", "starting_stack": 12.5}],
+//   "rounds": [{"id": 0, "street": "Preflop", "cards": [], "actions": [{"action_number": 1, "player_id": 0, "action": "Post SB", "amount": 0.05, "is_allin": false}]}],
+//   "pots": [{"number": 0, "amount": 1.5, "rake": 0.05, "player_wins": [{"player_id": 1, "win_amount": 1.45, "contributed_rake": 0}]}]}}
+
+use super::*;
+
+pub struct Ohh<'h>(pub &'h Hand);
+
+#[allow(dead_code)]
+pub fn to_ohh_json(hands: &[Hand], data: &str) -> Result<String, std::fmt::Error> {
+    to_json(&hands.iter().map(Ohh).collect::<Vec<_>>(), data)
+}
+
+struct OhhCards<'c>(&'c [Card]);
+
+struct OhhPlayer<'h> {
+    id: u8,
+    player: &'h Player,
+}
+
+struct OhhRound<'h> {
+    id: u8,
+    street: &'static str,
+    cards: &'h [Card],
+    actions: Vec<OhhAction<'h>>,
+}
+
+struct OhhAction<'h> {
+    number: u64,
+    player_id: u8,
+    action: &'static str,
+    amount: Float,
+    is_allin: bool,
+    cards: Option<&'h [Card]>,
+}
+
+struct OhhPot {
+    number: u8,
+    amount: Float,
+    rake: Float,
+    jackpot: Float,
+    player_wins: Vec<(u8, Float)>,
+}
+
+struct OhhWin(u8, Float);
+
+fn game_type(game_type: &str) -> (&'static str, &'static str) {
+    let g = game_type.to_lowercase();
+    let hi_lo = g.contains("hi/lo");
+    let game = if g.contains("omaha") {
+        if hi_lo { "OmahaHiLo" } else { "Omaha" }
+    } else if g.contains("razz") {
+        "Razz"
+    } else if g.contains("stud") {
+        if hi_lo { "StudHiLo" } else { "Stud" }
+    } else if g.contains("draw") || g.contains("badugi") {
+        "Draw"
+    } else {
+        "Holdem"
+    };
+    let bet_type = if g.contains("no limit") { "NL" } else if g.contains("pot limit") { "PL" } else { "FL" };
+    (game, bet_type)
+}
+
+fn street_name(street: &Street) -> &'static str {
+    const DRAWS: &[&str] = &["First Draw", "Second Draw", "Third Draw"];
+    match street.t {
+        StreetType::Flop => "Flop",
+        StreetType::Turn => "Turn",
+        StreetType::River => "River",
+        StreetType::Draw => DRAWS.get(street.index as usize).copied().unwrap_or("Third Draw"),
+        StreetType::Third => "Third Street",
+        StreetType::Fourth => "Fourth Street",
+        StreetType::Fifth => "Fifth Street",
+        StreetType::Sixth => "Sixth Street",
+        StreetType::Seventh => "Seventh Street",
+        StreetType::Showdown => "Showdown",
+    }
+}
+
+// Action numbers and player ids are filled in by the round
+fn ohh_action(action: &Action) -> Option<(Span, OhhAction<'_>)> {
+    let r = match action {
+        Action::Blind(t, name, amount) => {
+            let action = match t {
+                BlindType::Small => "Post SB",
+                BlindType::Big => "Post BB",
+                BlindType::SmallAndBig => "Post Dead",
+                BlindType::Ante => "Post Ante",
+                BlindType::BringIn => "Bet",
+            };
+            (*name, action, amount.amount, false, None)
+        }
+        Action::Play(play, name, data) => {
+            let (action, amount) = match (play, data) {
+                (Play::Check, _) => ("Check", 0.0),
+                (Play::Stand, _) => ("Stand Pat", 0.0),
+                (Play::Bet, Some(d)) => ("Bet", d.bet.amount),
+                (Play::Call, Some(d)) => ("Call", d.bet.amount),
+                (Play::Raise, Some(d)) => ("Raise", d.bet_to.unwrap_or(d.bet).amount),
+                _ => return None,
+            };
+            (*name, action, amount, data.is_some_and(|d| d.all_in), None)
+        }
+        Action::Fold(name, cards) => (*name, "Fold", 0.0, false, cards.as_deref()),
+        Action::Discard(name, _, cards) => (*name, "Discard", 0.0, false, cards.as_deref()),
+        Action::ShowHand(name, cards) => (*name, "Shows Cards", 0.0, false, Some(&cards[..])),
+        Action::Event(Event::NotShow, name) => (*name, "Mucks Cards", 0.0, false, None),
+        _ => return None,
+    };
+    let (name, action, amount, is_allin, cards) = r;
+    Some((name, OhhAction{number: 0, player_id: 0, action, amount, is_allin, cards}))
+}
+
+fn currency_code(c: &Currency) -> &'static str {
+    match c.symbol {
+        '$' => "USD",
+        '\u{20ac}' => "EUR",
+        '£' => "GBP",
+        '¥' => "CNY",
+        '\u{20b9}' => "INR",
+        _ => "",
+    }
+}
+
+impl<'h> Json for Ohh<'h> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        let hand = self.0;
+        let data = f.data;
+        let player_id = |name: &Span| hand.header.players.iter().position(|p| data[p.name] == data[*name]).map(|i| i as u8);
+
+        let info = &hand.header.info;
+        let (game, bet_type) = game_type(&data[info.game_type]);
+        let ante = hand.header.actions.iter().find_map(|a| match a {
+            Action::Blind(BlindType::Ante, _, amount) => Some(amount.amount),
+            _ => None,
+        });
+
+        let players = hand.header.players.iter().enumerate()
+            .map(|(i, player)| OhhPlayer{id: i as u8, player})
+            .collect::<Vec<_>>();
+
+        // Dealt cards, the blinds and the preflop actions are the first round
+        let mut number = 0;
+        let dealt = hand.hole_cards.dealt_to.iter().filter_map(|(name, cards)| {
+            let player_id = player_id(name)?;
+            number += 1;
+            Some(OhhAction{number, player_id, action: "Dealt Cards", amount: 0.0, is_allin: false, cards: Some(cards)})
+        }).collect::<Vec<_>>();
+        let mut round = |id: u8, street: &'static str, cards: &'h [Card], actions: &mut dyn Iterator<Item = &'h Action>| {
+            let actions = actions.filter_map(ohh_action).filter_map(|(name, action)| {
+                number += 1;
+                Some(OhhAction{number, player_id: player_id(&name)?, ..action})
+            }).collect();
+            OhhRound{id, street, cards, actions}
+        };
+        let mut rounds = vec![round(0, "Preflop", &[], &mut hand.header.actions.iter().chain(hand.hole_cards.actions.iter()))];
+        rounds[0].actions.splice(0..0, dealt);
+        for street in &hand.streets {
+            let cards: &[Card] = match (street.t, &street.cards, &street.new_card) {
+                (_, _, Some(new_card)) => std::slice::from_ref(new_card),
+                (StreetType::Flop, Some(cards), None) => cards,
+                _ => &[],
+            };
+            rounds.push(round(rounds.len() as u8, street_name(street), cards, &mut street.actions.iter()));
+        }
+
+        // Rake and jackpot are taken from the main pot
+        let summary = &hand.summary;
+        let mut pots = vec![OhhPot{
+            number: 0,
+            amount: summary.main_pot.amount,
+            rake: summary.rake.map_or(0.0, |r| r.amount),
+            jackpot: summary.jackpot.map_or(0.0, |j| j.amount),
+            player_wins: Vec::new(),
+        }];
+        for (i, side_pot) in summary.side_pots.iter().enumerate() {
+            pots.push(OhhPot{number: i as u8 + 1, amount: side_pot.amount, rake: 0.0, jackpot: 0.0, player_wins: Vec::new()});
+        }
+        for a in hand.actions() {
+            if let Action::CollectedPot(pot, name, amount, _) = a {
+                let Some(id) = player_id(name) else { continue };
+                let last = pots.len() - 1;
+                let pot = &mut pots[match pot { Pot::Main => 0, Pot::Side(n) => n.unwrap_or(1) as usize }.min(last)];
+                match pot.player_wins.iter_mut().find(|(p, _)| *p == id) {
+                    Some((_, win)) => *win += amount.amount,
+                    None => pot.player_wins.push((id, amount.amount)),
+                }
+            }
+        }
+
+        let ohh = OhhBody{hand, game, bet_type, ante: ante.unwrap_or(0.0), players, rounds, pots, hero: hand.hero().and_then(|h| player_id(&h))};
+        f.object().entry("ohh", &ohh).finish()
+    }
+}
+
+struct OhhBody<'h> {
+    hand: &'h Hand,
+    game: &'static str,
+    bet_type: &'static str,
+    ante: Float,
+    hero: Option<u8>,
+    players: Vec<OhhPlayer<'h>>,
+    rounds: Vec<OhhRound<'h>>,
+    pots: Vec<OhhPot>,
+}
+
+// Tournament hands name the tournament in the line with the hand number:
+// PokerStars Hand #208966141595: Tournament #2804163510, $0.98+$0.12 USD Hold'em No Limit - Level I (10/20)
+fn is_tournament(info: &HandInfo, data: &str) -> bool {
+    let id = info.id.begin as usize;
+    let begin = data[..id].rfind('\n').map_or(0, |i| i + 1);
+    let end = data[id..].find('\n').map_or(data.len(), |i| id + i);
+    data[begin..end].contains("Tournament")
+}
+
+struct OhhBetLimit(&'static str);
+
+impl Json for OhhBetLimit {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("bet_type", &self.0)
+            .entry("bet_cap", &0u8)
+            .finish()
+    }
+}
+
+impl<'h> Json for OhhBody<'h> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        let header = &self.hand.header;
+        let tournament = is_tournament(&header.info, f.data);
+        let mut o = f.object();
+        o.entry("spec_version", &"1.4.6")
+            .entry("site_name", &header.info.site_name)
+            .entry("network_name", &header.info.site_name)
+            .entry("internal_version", &env!("CARGO_PKG_VERSION"))
+            .entry("tournament", &tournament)
+            .entry("game_number", &header.info.id)
+            .entry("table_name", &header.table.name)
+            .entry("game_type", &self.game)
+            .entry("bet_limit", &OhhBetLimit(self.bet_type))
+            .entry("table_size", &header.table.max_players)
+            .entry("currency", &currency_code(&header.info.buy_in_max))
+            .entry("dealer_seat", &header.table.button)
+            .entry("small_blind_amount", &header.info.buy_in_min.amount)
+            .entry("big_blind_amount", &header.info.buy_in_max.amount)
+            .entry("ante_amount", &self.ante);
+        if let Some(hero) = self.hero {
+            o.entry("hero_player_id", &hero);
+        }
+        o.entry("flags", &if header.table.play_money { vec!["Play_Money"] } else { Vec::new() })
+            .entry("players", &self.players)
+            .entry("rounds", &self.rounds)
+            .entry("pots", &self.pots)
+            .finish()
+    }
+}
+
+impl<'c> Json for OhhCards<'c> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.write_char('[')?;
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 { f.write_char(',')?; }
+            write!(f, "\"{}\"", card)?;
+        }
+        f.write_char(']')
+    }
+}
+
+impl<'h> Json for OhhPlayer<'h> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("id", &self.id)
+            .entry("seat", &self.player.seat)
+            .entry("name", &self.player.name)
+            .entry("display", &self.player.name)
+            .entry("starting_stack", &self.player.chips.amount)
+            .entry("player_bounty", &self.player.bounty.map_or(0.0, |b| b.amount))
+            .finish()
+    }
+}
+
+impl<'h> Json for OhhRound<'h> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("id", &self.id)
+            .entry("street", &self.street)
+            .entry("cards", &OhhCards(self.cards))
+            .entry("actions", &self.actions)
+            .finish()
+    }
+}
+
+impl<'h> Json for OhhAction<'h> {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        let mut o = f.object();
+        o.entry("action_number", &self.number)
+            .entry("player_id", &self.player_id)
+            .entry("action", &self.action)
+            .entry("amount", &self.amount)
+            .entry("is_allin", &self.is_allin);
+        if let Some(cards) = self.cards {
+            o.entry("cards", &OhhCards(cards));
+        }
+        o.finish()
+    }
+}
+
+impl Json for OhhWin {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("player_id", &self.0)
+            .entry("win_amount", &self.1)
+            .entry("contributed_rake", &0u8)
+            .finish()
+    }
+}
+
+impl Json for OhhPot {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry("number", &self.number)
+            .entry("amount", &self.amount)
+            .entry("rake", &self.rake)
+            .entry("jackpot", &self.jackpot)
+            .entry("player_wins", &self.player_wins.iter().map(|(id, win)| OhhWin(*id, *win)).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluribus_hand() {
+        let data = std::fs::read_to_string("data/example/pluribus_example.txt").unwrap();
+        let hands = parse_string(&data);
+        let json = to_json(&Ohh(&hands[0]), &data).unwrap();
+        assert!(json.starts_with("{\"ohh\":{\"spec_version\":\"1.4.6\",\"site_name\":\"PokerStars\","));
+        assert!(json.contains("\"game_number\":\"33000\""));
+        assert!(json.contains("\"game_type\":\"Holdem\",\"bet_limit\":{\"bet_type\":\"NL\",\"bet_cap\":0}"));
+        assert!(json.contains("{\"id\":3,\"seat\":4,\"name\":\"Eddie\""));
+        assert!(json.contains("\"tournament\":false"));
+        assert!(json.contains("{\"action_number\":1,\"player_id\":0,\"action\":\"Dealt Cards\",\"amount\":0,\"is_allin\":false,\"cards\":[\"9d\",\"6c\"]}"));
+        assert!(json.contains("{\"action_number\":6,\"player_id\":5,\"action\":\"Dealt Cards\",\"amount\":0,\"is_allin\":false,\"cards\":[\"2d\",\"Jc\"]}"));
+        assert_eq!(json.matches("\"Dealt Cards\"").count(), hands[0].hole_cards.dealt_to.len());
+        assert!(json.contains("{\"action_number\":7,\"player_id\":0,\"action\":\"Post SB\",\"amount\":50,\"is_allin\":false}"));
+        assert!(json.contains("\"action\":\"Raise\",\"amount\":900,"));
+        assert!(json.contains("{\"id\":1,\"street\":\"Flop\",\"cards\":[\"Qs\",\"7h\",\"5s\"],"));
+        assert!(json.contains("{\"id\":2,\"street\":\"Turn\",\"cards\":[\"4h\"],"));
+        assert!(json.ends_with("\"pots\":[{\"number\":0,\"amount\":4350,\"rake\":0,\"jackpot\":0,\"player_wins\":[{\"player_id\":3,\"win_amount\":4350,\"contributed_rake\":0}]}]}}"));
+
+        let all = to_ohh_json(&hands, &data).unwrap();
+        assert!(all.starts_with("[{\"ohh\":"));
+    }
+
+    #[test]
+    fn tournament_hand() {
+        let data = "PokerStars Hand #208966141595: Tournament #2804163510, $0.98+$0.12 USD Hold'em No Limit - Level I (10/20) - 2020/02/08 4:01:19 ET
+Table '2804163510 1' 9-max Seat #1 is the button
+Seat 1: pondtree312 (1500 in chips)
+Seat 2: Rifama (1500 in chips)
+pondtree312: posts small blind 10
+Rifama: posts big blind 20
+*** HOLE CARDS ***
+Dealt to Rifama [Qh Qd]
+pondtree312: folds
+Uncalled bet (10) returned to Rifama
+Rifama collected 20 from pot
+*** SUMMARY ***
+Total pot 20 | Rake 0
+";
+        let hands = parse_string(data);
+        let json = to_json(&Ohh(&hands[0]), data).unwrap();
+        assert!(json.contains("\"tournament\":true"));
+    }
+
+    #[test]
+    fn side_pots() {
+        let data = "PokerStars Hand #208966141602:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($5 in chips)
+Seat 2: p2 ($10 in chips)
+Seat 3: p3 ($20 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $19.50 to $20 and is all-in
+p4: calls $20
+p1: calls $4.75 and is all-in
+p2: calls $9.50 and is all-in
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [Qh Qc] (a pair of Queens)
+p4: shows [Jh Jc] (a pair of Jacks)
+p3 collected $14 from side pot-1
+p3 collected $20 from side pot-2
+p1 collected $19 from main pot
+*** SUMMARY ***
+Total pot $55 Main pot $19. Side pot-1 $14. Side pot-2 $20. | Rake $2
+";
+        let hands = parse_string(data);
+        let json = to_json(&Ohh(&hands[0]), data).unwrap();
+        assert!(json.contains("{\"number\":1,\"amount\":14,\"rake\":0,\"jackpot\":0,\"player_wins\":[{\"player_id\":2,\"win_amount\":14,\"contributed_rake\":0}]}"));
+        assert!(json.contains("{\"number\":2,\"amount\":20,\"rake\":0,\"jackpot\":0,\"player_wins\":[{\"player_id\":2,\"win_amount\":20,\"contributed_rake\":0}]}"));
+    }
+}
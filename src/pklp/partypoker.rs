@@ -0,0 +1,222 @@
+//region PartyPoker
+
+// ***** Hand History for Game 19233040011 *****
+// $0.25/$0.50 USD NL Texas Hold'em - Saturday, February 08, 04:01:19 ET 2020
+// Table Acamar (Real Money)
+// Seat 3 is the button
+// Total number of players : 3/6
+// Seat 1: pondtree312 ( $49.50 USD )
+// pondtree312 posts small blind [$0.25 USD].
+// ** Dealing down cards **
+// Dealt to spr1teg [  8s Jd ]
+// spr1teg raises [$1.25 USD]
+// ** Dealing Flop ** [ 8d, 3c, Ad ]
+// spr1teg shows [ 8s, Jd ]a pair of Eights.
+// spr1teg wins $2.85 USD from the main pot with a pair of Eights.
+
+use super::*;
+
+pub struct PartyPoker;
+
+impl SiteParser for PartyPoker {
+    fn name(&self) -> &'static str { "PartyPoker" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        hand_blocks(data, is_hand_header).iter().filter_map(|lines| parse_hand(lines, data)).collect()
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("***** Hand History for Game ")
+}
+
+fn parse_hand(lines: &[&str], data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut lines = lines.iter().copied().peekable();
+
+    // Party does not write its name, the title is what tells its hands apart
+    let title = lines.next()?.trim_start_matches('\u{feff}');
+    let site = title.trim_start_matches(['*', ' ']).prefix_str(" for ");
+    let hand = &title["***** Hand History for ".len()..][.."Game".len()];
+    let id = title["***** Hand History for Game ".len()..].trim_end_matches('*').trim();
+
+    // $0.25/$0.50 USD NL Texas Hold'em - Saturday, February 08, 04:01:19 ET 2020
+    let game = lines.next()?;
+    let (stakes, rest) = game.split_once(' ')?;
+    let (buy_in_min, buy_in_max) = parse_stakes(stakes)?;
+    let rest = match rest.split_once(' ') {
+        Some((code, rest)) if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) => rest,
+        _ => rest,
+    };
+    let game_type = &rest[..rest.find(" - ").unwrap_or(rest.len())];
+
+    let table_line = lines.next()?;
+    let table_name = table_line.strip_prefix("Table ")?;
+    let table_name = &table_name[..table_name.find(" (").unwrap_or(table_name.len())];
+    let play_money = table_line.contains("(Play Money)");
+
+    let mut b = HandBuilder::default();
+    let mut button = 0;
+    let mut max_players = 0;
+    while let Some(line) = lines.peek().copied() {
+        if let Some(seat) = parse_button(line) {
+            button = seat;
+        } else if line.starts_with("Total number of players") {
+            max_players = line.rsplit('/').next().and_then(|m| parse_integer(m.trim())).map_or(0, |(v, _)| v as u8);
+        } else if !b.seat(line, data) {
+            break;
+        }
+        lines.next();
+    }
+
+    let info = HandInfo{
+        site_name: "PartyPoker", site: span(site), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(game_type), buy_in_min, buy_in_max,
+    };
+    let table = Table{name: span(table_name), max_players, button, play_money};
+    let header = Header{info, table, players: PlayerVec::new(), actions: ActionVec::new()};
+
+    for line in lines {
+        if let Some(street) = line.strip_prefix("** Dealing ") {
+            // ** Dealing down cards **, ** Dealing Flop ** [ 8d, 3c, Ad ]
+            let t = match street.split(' ').next()? {
+                "Flop" => StreetType::Flop,
+                "Turn" => StreetType::Turn,
+                "River" => StreetType::River,
+                _ => { b.hole_cards(); continue; }
+            };
+            b.deal_board(t, parse_cards(line.rbetween('[', ']')));
+        }
+        else if let Some((name, rest)) = b.player(line, data) {
+            parse_action(&mut b, span(name), name, rest);
+        }
+        else {
+            b.dealt(line, data);
+        }
+    }
+
+    Some(b.finish(header, None, data))
+}
+
+fn parse_action<'a>(b: &mut HandBuilder<'a>, span: Span, name: &'a str, rest: &str) {
+    let amount = || parse_currency_any(rest.between('[', ']'));
+    if rest.starts_with("posts ") {
+        let t = if rest.contains("+ dead") {
+            BlindType::SmallAndBig
+        } else if rest.starts_with("posts small") {
+            BlindType::Small
+        } else if rest.starts_with("posts ante") {
+            BlindType::Ante
+        } else {
+            BlindType::Big
+        };
+        if let Some(amount) = amount() { b.blind(name, span, t, amount); }
+    }
+    else if rest.starts_with("calls ") || rest.starts_with("bets ") || rest.starts_with("raises ") || rest.starts_with("is all-In") {
+        if let Some(amount) = amount() { b.play(name, span, amount, rest.starts_with("is all-In")); }
+    }
+    else if let Some(won) = rest.strip_prefix("wins ") {
+        // wins $2.85 USD from the main pot with a pair of Eights.
+        // wins $1 USD from the side pot #1 with a flush.
+        let pot = parse_pot(won);
+        if let Some(amount) = parse_currency_any(won.split(" from ").next().unwrap_or(won)) {
+            b.push(Action::CollectedPot(pot, span, amount, None));
+        }
+    }
+    else {
+        b.common_action(span, rest);
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_party_hand() {
+        let data = "***** Hand History for Game 19233040011 *****
+$0.25/$0.50 USD NL Texas Hold'em - Saturday, February 08, 04:01:19 ET 2020
+Table Acamar (Real Money)
+Seat 3 is the button
+Total number of players : 3/6
+Seat 1: pondtree312 ( $49.50 USD )
+Seat 2: Rifama ( $50 USD )
+Seat 3: spr1teg ( $50.50 USD )
+pondtree312 posts small blind [$0.25 USD].
+Rifama posts big blind [$0.50 USD].
+** Dealing down cards **
+Dealt to spr1teg [  8s Jd ]
+spr1teg raises [$1.25 USD]
+pondtree312 calls [$1 USD]
+Rifama folds
+** Dealing Flop ** [ 8d, 3c, Ad ]
+pondtree312 checks
+spr1teg bets [$1 USD]
+pondtree312 raises [$3 USD]
+spr1teg calls [$2 USD]
+** Dealing Turn ** [ 9c ]
+pondtree312 checks
+spr1teg checks
+** Dealing River ** [ 6d ]
+pondtree312 checks
+spr1teg checks
+pondtree312 shows [ 5d, Ks ]high card Ace.
+spr1teg shows [ 8s, Jd ]a pair of Eights.
+spr1teg wins $8.85 USD from the main pot with a pair of Eights.
+
+";
+        assert!(detect_site(data).is_some_and(|site| site.name() == "PartyPoker"));
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(hand.header.info.site_name, "PartyPoker");
+        assert_eq!(&data[hand.header.info.site], "Hand History");
+        assert_eq!(&data[hand.header.info.id], "19233040011");
+        assert_eq!(&data[hand.header.info.game_type], "NL Texas Hold'em");
+        assert_eq!(hand.header.info.buy_in_max.amount, 0.5);
+        assert_eq!(&data[hand.header.table.name], "Acamar");
+        assert_eq!(hand.header.table.button, 3);
+        assert_eq!(hand.header.table.max_players, 6);
+        assert_eq!(hand.header.players.len(), 3);
+        assert_eq!(hand.header.actions.len(), 2);
+        assert_eq!(&data[hand.hero().unwrap()], "spr1teg");
+
+        match &hand.hole_cards.actions[0] {
+            Action::Play(Play::Raise, _, Some(d)) => {
+                assert_eq!(d.bet.amount, 0.75);
+                assert_eq!(d.bet_to.unwrap().amount, 1.25);
+            }
+            a => panic!("Unexpected action {:?}", a),
+        }
+        assert!(matches!(hand.hole_cards.actions[1], Action::Play(Play::Call, _, _)));
+
+        assert_eq!(hand.streets.len(), 4);
+        let flop = &hand.streets[0];
+        assert_eq!(flop.cards.as_ref().unwrap().len(), 3);
+        assert!(matches!(flop.actions[1], Action::Play(Play::Bet, _, _)));
+        assert!(matches!(flop.actions[2], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, ..}), ..})) if amount == 3.0));
+        let turn = &hand.streets[1];
+        assert_eq!(turn.cards.as_ref().unwrap().len(), 3);
+        assert!(turn.new_card.is_some());
+        assert!(matches!(hand.streets[3].t, StreetType::Showdown));
+        assert!(matches!(hand.streets[3].actions[2], Action::CollectedPot(Pot::Main, _, _, None)));
+
+        // Party does not write the rake, the pot is what the players put in
+        assert_eq!(hand.summary.pot.amount, 9.0);
+        assert_eq!(hand.summary.boards[0].len(), 5);
+        assert_eq!(hand.summary.seats.len(), 2);
+        assert_eq!(hand.summary.seats[1].won.len(), 1);
+    }
+}
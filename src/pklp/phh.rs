@@ -0,0 +1,537 @@
+//region PHH
+
+// Poker Hand History, the TOML format of the academic hand datasets
+//
+// variant = "NT"
+// antes = [0, 0, 0, 0, 0, 0]
+// blinds_or_straddles = [50, 100, 0, 0, 0, 0]
+// min_bet = 100
+// starting_stacks = [10000, 10000, 10000, 10000, 10000, 10000]
+// actions = [
+//   "d dh p1 9d6c",
+//   "p3 f",
+//   "p4 cbr 200",
+//   "d db Qs7h5s",
+//   "p4 sm 6d6h",
+// ]
+// players = ["MrWhite", "Gogo", "Budd", "Eddie", "Bill", "Pluribus"]
+//
+// Players are ordered from the seat after the button, the button acts last.
+
+use super::*;
+
+pub struct Phh;
+
+impl SiteParser for Phh {
+    fn name(&self) -> &'static str { "PHH" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        parse_toml(data).iter().filter_map(|fields| parse_hand(fields, data)).collect()
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').strip_prefix("variant").is_some_and(|r| r.trim_start().starts_with('='))
+}
+
+//region Write
+
+#[allow(dead_code)]
+pub fn to_phh(hand: &Hand, data: &str) -> String {
+    let players = hand.players_after_button();
+    let n = players.len();
+    let index = |name: &Span| players.iter().position(|p| data[p.name] == data[*name]);
+    let num = format_amount;
+    let list = |v: &[Float]| v.iter().map(|v| num(*v)).collect::<Vec<_>>().join(", ");
+    let cards = |cards: &[Card]| cards.iter().map(|c| c.to_string()).collect::<String>();
+
+    let variant = phh_variant(&data[hand.header.info.game_type]);
+    let is_stud = matches!(variant, "F7S" | "F7S/8" | "FR");
+    let mut antes = vec![0.0; n];
+    let mut blinds = vec![0.0; n];
+    let mut committed = vec![0.0; n];
+    let mut actions = Vec::new();
+
+    for a in &hand.header.actions {
+        match a {
+            Action::Blind(BlindType::Ante, name, amount) => if let Some(i) = index(name) { antes[i] += amount.amount },
+            Action::Blind(BlindType::BringIn, ..) => {}
+            Action::Blind(_, name, amount) => if let Some(i) = index(name) {
+                blinds[i] += amount.amount;
+                committed[i] += amount.amount;
+            },
+            _ => {}
+        }
+    }
+
+    if !is_stud {
+        // Hidden cards are written as question marks, one per card
+        let count = hand.hole_cards.dealt_to.first().map_or(match variant { "PO" | "FO/8" => 4, "F2L3D" | "N2L1D" => 5, _ => 2 }, |(_, c)| c.len());
+        for (i, p) in players.iter().enumerate() {
+            let dealt = hand.hole_cards.dealt_to.iter().find(|(name, _)| data[*name] == data[p.name]);
+            let dealt = dealt.map_or_else(|| "??".repeat(count), |(_, c)| cards(c));
+            actions.push(format!("d dh p{} {}", i + 1, dealt));
+        }
+    }
+
+    let streets = std::iter::once((None, &hand.hole_cards.actions)).chain(hand.streets.iter().map(|s| (Some(s), &s.actions)));
+    for (street, street_actions) in streets {
+        let mut dealt = Vec::new();
+        if let Some(street) = street {
+            if street.index > 0 && matches!(street.t, StreetType::Flop | StreetType::Turn | StreetType::River) {
+                continue;
+            }
+            committed.iter_mut().for_each(|c| *c = 0.0);
+            match street.t {
+                StreetType::Flop => actions.push(format!("d db {}", cards(street.cards.as_deref().unwrap_or(&[])))),
+                StreetType::Turn | StreetType::River => actions.extend(street.new_card.map(|c| format!("d db {}", c))),
+                StreetType::Draw => for (name, c, new) in &street.dealt_to {
+                    dealt.extend(index(name).map(|i| format!("d dh p{} {}", i + 1, cards(new.as_ref().unwrap_or(c)))));
+                },
+                StreetType::Showdown => {}
+                _ => for (name, c) in street.down_cards.iter().chain(&street.up_cards) {
+                    actions.extend(index(name).map(|i| format!("d dh p{} {}", i + 1, cards(c))));
+                },
+            }
+        }
+
+        for a in street_actions.iter() {
+            // Replacement cards follow the discards
+            if !matches!(a, Action::Discard(..) | Action::Play(Play::Stand, ..)) {
+                actions.append(&mut dealt);
+            }
+            let line = match a {
+                Action::Fold(name, _) => index(name).map(|i| format!("p{} f", i + 1)),
+                Action::Play(Play::Check, name, _) => index(name).map(|i| format!("p{} cc", i + 1)),
+                Action::Play(Play::Call, name, d) => index(name).map(|i| {
+                    committed[i] += d.map_or(0.0, |d| d.bet.amount);
+                    format!("p{} cc", i + 1)
+                }),
+                Action::Play(Play::Bet | Play::Raise, name, Some(d)) => index(name).map(|i| {
+                    committed[i] = d.bet_to.map_or(committed[i] + d.bet.amount, |to| to.amount);
+                    format!("p{} cbr {}", i + 1, num(committed[i]))
+                }),
+                Action::Play(Play::Stand, name, _) => index(name).map(|i| format!("p{} sd", i + 1)),
+                Action::Discard(name, count, discarded) => index(name).map(|i| {
+                    let discarded = discarded.as_ref().map_or_else(|| "??".repeat(*count as usize), |c| cards(c));
+                    format!("p{} sd {}", i + 1, discarded)
+                }),
+                Action::Blind(BlindType::BringIn, name, amount) => index(name).map(|i| {
+                    committed[i] += amount.amount;
+                    format!("p{} pb", i + 1)
+                }),
+                Action::ShowHand(name, c) => index(name).map(|i| format!("p{} sm {}", i + 1, cards(c))),
+                Action::Event(Event::NotShow, name) => index(name).map(|i| format!("p{} sm", i + 1)),
+                _ => None,
+            };
+            actions.extend(line);
+        }
+        actions.append(&mut dealt);
+    }
+
+    let mut winnings = vec![0.0; n];
+    for a in hand.actions() {
+        if let Action::CollectedPot(_, name, amount, _) = a {
+            if let Some(i) = index(name) { winnings[i] += amount.amount; }
+        }
+    }
+
+    let mut out = String::new();
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    writeln!(out, "variant = {}", quote(variant)).unwrap();
+    writeln!(out, "antes = [{}]", list(&antes)).unwrap();
+    writeln!(out, "blinds_or_straddles = [{}]", list(&blinds)).unwrap();
+    writeln!(out, "min_bet = {}", num(hand.header.info.buy_in_max.amount)).unwrap();
+    writeln!(out, "starting_stacks = [{}]", list(&players.iter().map(|p| p.chips.amount).collect::<Vec<_>>())).unwrap();
+    writeln!(out, "actions = [").unwrap();
+    for a in &actions {
+        writeln!(out, "  {},", quote(a)).unwrap();
+    }
+    writeln!(out, "]").unwrap();
+    let id = &data[hand.header.info.id];
+    if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+        writeln!(out, "hand = {}", id).unwrap();
+    }
+    writeln!(out, "table = {}", quote(&data[hand.header.table.name])).unwrap();
+    if hand.header.table.max_players > 0 {
+        writeln!(out, "seat_count = {}", hand.header.table.max_players).unwrap();
+    }
+    writeln!(out, "seats = [{}]", players.iter().map(|p| p.seat.to_string()).collect::<Vec<_>>().join(", ")).unwrap();
+    writeln!(out, "players = [{}]", players.iter().map(|p| quote(&data[p.name])).collect::<Vec<_>>().join(", ")).unwrap();
+    writeln!(out, "winnings = [{}]", list(&winnings)).unwrap();
+    if let Some(currency) = currency_code(hand.header.info.buy_in_max.symbol) {
+        writeln!(out, "currency = {}", quote(currency)).unwrap();
+    }
+    if let Some(rake) = hand.summary.rake {
+        writeln!(out, "_rake = {}", num(rake.amount)).unwrap();
+    }
+    out
+}
+
+#[allow(dead_code)]
+fn phh_variant(game_type: &str) -> &'static str {
+    let g = game_type.to_lowercase();
+    let hi_lo = g.contains("hi/lo");
+    if g.contains("razz") { "FR" }
+    else if g.contains("stud") { if hi_lo { "F7S/8" } else { "F7S" } }
+    else if g.contains("omaha") { if hi_lo { "FO/8" } else { "PO" } }
+    else if g.contains("badugi") { "FB" }
+    else if g.contains("single draw") { "N2L1D" }
+    else if g.contains("draw") { "F2L3D" }
+    else if g.contains("limit") && !g.contains("no limit") && !g.contains("pot limit") { "FT" }
+    else { "NT" }
+}
+
+#[allow(dead_code)]
+fn currency_code(symbol: char) -> Option<&'static str> {
+    match symbol {
+        '$' => Some("USD"),
+        '\u{20ac}' => Some("EUR"),
+        '£' => Some("GBP"),
+        _ => None,
+    }
+}
+
+//endregion
+
+//region Read
+
+#[derive(Debug)]
+enum Value<'a> {
+    Str(&'a str),
+    // Numbers, booleans and dates
+    Num(&'a str),
+    Array(Vec<Value<'a>>),
+}
+
+type Fields<'a> = Vec<(&'a str, Value<'a>)>;
+
+fn skip_space(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        match s.strip_prefix('#') {
+            Some(comment) => s = &comment[comment.find('\n').unwrap_or(comment.len())..],
+            None => return s,
+        }
+    }
+}
+
+fn parse_value(s: &str) -> Option<(Value<'_>, &str)> {
+    if let Some(r) = s.strip_prefix('"') {
+        let mut escaped = false;
+        let end = r.char_indices().find(|&(_, c)| {
+            let end = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            end
+        })?.0;
+        Some((Value::Str(&r[..end]), &r[end + 1..]))
+    } else if let Some(mut r) = s.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            r = skip_space(r);
+            if let Some(r) = r.strip_prefix(']') {
+                return Some((Value::Array(items), r));
+            }
+            let (v, rest) = parse_value(r)?;
+            items.push(v);
+            r = skip_space(rest);
+            r = r.strip_prefix(',').unwrap_or(r);
+        }
+    } else {
+        let end = s.find(|c: char| !(c.is_ascii_alphanumeric() || "+-._:".contains(c))).unwrap_or(s.len());
+        (end > 0).then(|| (Value::Num(&s[..end]), &s[end..]))
+    }
+}
+
+fn parse_toml(data: &str) -> Vec<Fields<'_>> {
+    // Hands of a .phhs file are in [n] tables
+    let mut tables = vec![Fields::new()];
+    let mut rest = skip_space(data.trim_start_matches('\u{feff}'));
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('[') {
+            rest = &r[r.find(']').map_or(r.len(), |i| i + 1)..];
+            tables.push(Fields::new());
+        } else {
+            let Some(eq) = rest.find('=') else { break };
+            let key = rest[..eq].trim();
+            let Some((value, r)) = parse_value(skip_space(&rest[eq + 1..])) else { break };
+            tables.last_mut().unwrap().push((key, value));
+            rest = r;
+        }
+        rest = skip_space(rest);
+    }
+    tables.retain(|t| t.iter().any(|(k, _)| *k == "variant"));
+    tables
+}
+
+fn field<'f, 'a>(fields: &'f Fields<'a>, key: &str) -> Option<&'f Value<'a>> {
+    fields.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+}
+
+fn numbers(value: Option<&Value>) -> Vec<Float> {
+    match value {
+        Some(Value::Array(items)) => items.iter().map(|v| match v {
+            Value::Num(n) => n.parse().unwrap_or(0.0),
+            _ => 0.0,
+        }).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_hand(fields: &Fields, data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let Some(Value::Str(variant)) = field(fields, "variant") else { return None };
+    let Some(Value::Array(actions)) = field(fields, "actions") else { return None };
+    let actions = actions.iter().filter_map(|a| match a { Value::Str(s) => Some(*s), _ => None }).collect::<Vec<_>>();
+
+    let symbol = match field(fields, "currency") {
+        Some(Value::Str("USD")) => '$',
+        Some(Value::Str("EUR")) => '\u{20ac}',
+        Some(Value::Str("GBP")) => '£',
+        _ => NO_CURRENCY,
+    };
+    let currency = |amount: Float| Currency{symbol, amount};
+    let stacks = numbers(field(fields, "starting_stacks"));
+    let antes = numbers(field(fields, "antes"));
+    let blinds = numbers(field(fields, "blinds_or_straddles"));
+    let seats = numbers(field(fields, "seats"));
+    let n = stacks.len();
+
+    // Without player names the "p1" of the actions is used
+    let names = (0..n).map(|i| {
+        if let Some(Value::Array(players)) = field(fields, "players") {
+            if let Some(Value::Str(name)) = players.get(i) {
+                return *name;
+            }
+        }
+        let p = format!("p{}", i + 1);
+        actions.iter().find_map(|a| a.split(' ').find(|t| *t == p)).unwrap_or(&variant[..0])
+    }).collect::<Vec<_>>();
+
+    let mut players = PlayerVec::new();
+    for i in 0..n {
+        let seat = seats.get(i).map_or(i as u8 + 1, |s| *s as u8);
+        players.push(Player{name: span(names[i]), seat, chips: currency(stacks[i]), bounty: None});
+    }
+    let button = players.last().map_or(0, |p| p.seat);
+
+    let mut posted = blinds.iter().copied().enumerate().filter(|(_, b)| *b > 0.0).collect::<Vec<_>>();
+    posted.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let big_blind = posted.last().map_or(0.0, |b| b.1);
+    let small_blind = posted.first().map_or(0.0, |b| b.1);
+    let min_bet = match field(fields, "min_bet") {
+        Some(Value::Num(n)) => n.parse().unwrap_or(big_blind),
+        _ => big_blind,
+    };
+
+    let (hand, id) = match fields.iter().find(|(k, _)| *k == "hand") {
+        Some((k, Value::Num(id) | Value::Str(id))) => (*k, *id),
+        _ => (&variant[..0], &variant[..0]),
+    };
+    let table_name = match field(fields, "table") {
+        Some(Value::Num(t) | Value::Str(t)) => *t,
+        _ => &variant[..0],
+    };
+    let max_players = match field(fields, "seat_count") {
+        Some(Value::Num(c)) => c.parse().unwrap_or(n as u8),
+        _ => n as u8,
+    };
+
+    let info = HandInfo{
+        site_name: "PHH", site: span(&variant[..0]), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(variant), buy_in_min: currency(small_blind), buy_in_max: currency(min_bet.max(big_blind)),
+    };
+    let table = Table{name: span(table_name), max_players, button, play_money: false};
+    let header = Header{info, table, players, actions: ActionVec::new()};
+
+    let mut b = HandBuilder::default();
+    let mut invested = vec![0.0; n];
+    for (i, ante) in antes.iter().enumerate().filter(|(i, a)| **a > 0.0 && *i < n) {
+        invested[i] += ante;
+        b.blind(names[i], span(names[i]), BlindType::Ante, currency(*ante));
+    }
+    for (k, &(i, blind)) in posted.iter().enumerate().filter(|(_, (i, _))| *i < n) {
+        let t = if k == 0 && posted.len() > 1 && blind < big_blind { BlindType::Small } else { BlindType::Big };
+        invested[i] += blind;
+        b.blind(names[i], span(names[i]), t, currency(blind));
+    }
+    b.hole_cards();
+
+    let mut boards = 0;
+    for action in actions {
+        let mut tokens = action.split(' ');
+        let (Some(actor), Some(verb)) = (tokens.next(), tokens.next()) else { continue };
+        let arg = tokens.next();
+        if actor == "d" {
+            match (verb, arg) {
+                ("db", Some(cards)) => {
+                    let t = [StreetType::Flop, StreetType::Turn, StreetType::River][boards.min(2)];
+                    boards += 1;
+                    b.deal_board(t, parse_phh_cards(cards));
+                }
+                ("dh", Some(player)) => {
+                    let Some(i) = player_index(player, n) else { continue };
+                    let cards = parse_phh_cards(tokens.next().unwrap_or(""));
+                    if cards.is_empty() { continue; }
+                    match b.streets.last_mut() {
+                        Some(street) => street.dealt_to.push((span(names[i]), cards, None)),
+                        None => b.hole_cards().dealt_to.push((span(names[i]), cards)),
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some(i) = player_index(actor, n) else { continue };
+        let (name, name_span) = (names[i], span(names[i]));
+        let committed = b.committed.get(name).copied().unwrap_or(0.0);
+        let remaining = stacks[i] - invested[i];
+        match (verb, arg) {
+            ("f", _) => b.push(Action::Fold(name_span, None)),
+            ("cc", _) => {
+                let added = (b.to_call - committed).min(remaining);
+                if added <= 0.0 {
+                    b.push(Action::Play(Play::Check, name_span, None));
+                } else {
+                    invested[i] += added;
+                    b.play(name, name_span, currency(added), added >= remaining - 0.001);
+                }
+            }
+            ("cbr", Some(amount)) => {
+                let added = amount.parse::<Float>().unwrap_or(0.0) - committed;
+                invested[i] += added;
+                b.play(name, name_span, currency(added), added >= remaining - 0.001);
+            }
+            ("sd", cards) => {
+                // The first discard after a betting round starts a draw
+                let drawing = matches!(b.streets.last(), Some(s) if matches!(s.t, StreetType::Draw) && s.actions.iter().all(|a| matches!(a, Action::Discard(..) | Action::Play(Play::Stand, ..))));
+                if !drawing {
+                    b.street(StreetType::Draw, None, None);
+                }
+                match cards.map(parse_phh_cards).filter(|c| !c.is_empty()) {
+                    Some(cards) => b.push(Action::Discard(name_span, cards.len() as u8, Some(cards))),
+                    None => match cards {
+                        Some(hidden) => b.push(Action::Discard(name_span, (hidden.len() / 2) as u8, None)),
+                        None => b.push(Action::Play(Play::Stand, name_span, None)),
+                    },
+                }
+            }
+            ("sm", Some(cards)) => {
+                b.showdown();
+                b.push(Action::ShowHand(name_span, parse_phh_cards(cards)));
+            }
+            ("sm", None) => b.push(Action::Event(Event::NotShow, name_span)),
+            _ => {}
+        }
+    }
+
+    for (i, won) in numbers(field(fields, "winnings")).into_iter().enumerate().filter(|(i, w)| *w > 0.0 && *i < n) {
+        b.push(Action::CollectedPot(Pot::Main, span(names[i]), currency(won), None));
+    }
+    let rake = match field(fields, "_rake") {
+        Some(Value::Num(r)) => r.parse().ok().map(currency),
+        _ => None,
+    };
+    Some(b.finish(header, rake, data))
+}
+
+fn player_index(player: &str, n: usize) -> Option<usize> {
+    player.strip_prefix('p')?.parse::<usize>().ok().filter(|i| (1..=n).contains(i)).map(|i| i - 1)
+}
+
+fn parse_phh_cards(s: &str) -> Cards {
+    // 9d6c, ????, Qs7h5s
+    let mut cards = Cards::new();
+    let chars = s.chars().collect::<Vec<_>>();
+    for c in chars.chunks_exact(2) {
+        if "23456789TJQKA".contains(c[0]) && "shdc".contains(c[1]) {
+            cards.extend(parse_cards(&c.iter().collect::<String>()));
+        }
+    }
+    cards
+}
+
+//endregion
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_pokerstars() {
+        let data = std::fs::read_to_string("data/example/pluribus_example.txt").unwrap();
+        let hands = parse_string(&data);
+        let phh = to_phh(&hands[0], &data);
+        assert!(phh.starts_with("variant = \"NT\"\n"));
+        assert!(phh.contains("blinds_or_straddles = [50, 100, 0, 0, 0, 0]"));
+        assert!(phh.contains("  \"d dh p1 9d6c\",\n"));
+        assert!(phh.contains("  \"p4 cbr 200\",\n"));
+        assert!(phh.contains("  \"p2 cbr 900\",\n"));
+        assert!(phh.contains("  \"d db Qs7h5s\",\n"));
+        assert!(phh.contains("  \"p4 sm 6d6h\",\n"));
+        assert!(phh.contains("players = [\"MrWhite\", \"Gogo\", \"Budd\", \"Eddie\", \"Bill\", \"Pluribus\"]"));
+
+        assert!(detect_site(&phh).is_some_and(|site| site.name() == "PHH"));
+        let read = parse_string(&phh);
+        assert_eq!(read.len(), 1);
+        assert_eq!(to_phh(&read[0], &phh), phh);
+
+        let (a, b) = (&hands[0], &read[0]);
+        assert_eq!(b.header.table.button, a.header.table.button);
+        assert_eq!(b.hole_cards.dealt_to.len(), a.hole_cards.dealt_to.len());
+        assert_eq!(b.streets.len(), a.streets.len());
+        assert_eq!(b.actions().count(), a.actions().filter(|a| !matches!(a, Action::UncalledBetReturned(..))).count());
+        assert_eq!(b.summary.pot.amount, a.summary.pot.amount);
+        assert_eq!(b.summary.boards[0].len(), 5);
+    }
+
+    #[test]
+    fn read_without_names() {
+        let data = r#"
+# Heads-up, the big blind acts first after the flop
+variant = "NT"
+antes = [0, 0]
+blinds_or_straddles = [2, 1]
+min_bet = 2
+starting_stacks = [200, 50]
+actions = [
+  "d dh p1 AhKd",
+  "d dh p2 ????",
+  "p2 cbr 6",
+  "p1 cc",
+  "d db 8d3cAd",
+  "p1 cbr 10",
+  "p2 cbr 44",
+  "p1 cc",
+  "d db 9c",
+  "d db 6d",
+  "p1 sm AhKd",
+  "p2 sm JcJd",
+]
+winnings = [100, 0]
+"#;
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.players[1].name], "p2");
+        assert!(matches!(hand.header.actions[0], Action::Blind(BlindType::Small, name, _) if &data[name] == "p2"));
+        assert!(matches!(hand.hole_cards.actions[0], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, ..}), ..})) if amount == 6.0));
+        assert!(matches!(hand.streets[0].actions[1], Action::Play(Play::Raise, _, Some(ActionData{all_in: true, ..}))));
+        assert!(matches!(hand.streets[0].actions[2], Action::Play(Play::Call, _, Some(ActionData{bet: Currency{amount, ..}, ..})) if amount == 34.0));
+        assert_eq!(hand.streets.len(), 4);
+        assert_eq!(hand.summary.pot.amount, 100.0);
+    }
+}
@@ -0,0 +1,196 @@
+//region 888poker
+
+// #Game No : 1234567890
+// ***** 888poker Hand History for Game 1234567890 *****
+// $0.25/$0.50 Blinds No Limit Holdem - *** 08 02 2020 04:01:19
+// Table Sparta 6 Max (Real Money)
+// Seat 3 is the button
+// Total number of players : 3
+// Seat 1: pondtree312 ( $49.50 )
+// pondtree312 posts small blind [$0.25]
+// ** Dealing down cards **
+// Dealt to spr1teg [ 8s, Jd ]
+// spr1teg raises [$1.25]
+// ** Dealing flop ** [ 8d, 3c, Ad ]
+// ** Summary **
+// spr1teg collected [ $2.85 ]
+
+use super::*;
+
+pub struct Poker888;
+
+impl SiteParser for Poker888 {
+    fn name(&self) -> &'static str { "888poker" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(|l| l.contains("***** 888poker Hand History for "))
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        hand_blocks(data, is_hand_header).iter().filter_map(|lines| parse_hand(lines, data)).collect()
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("#Game No : ")
+}
+
+fn parse_hand(lines: &[&str], data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut lines = lines.iter().copied().peekable();
+
+    let id = lines.next()?.trim_start_matches('\u{feff}')["#Game No : ".len()..].trim();
+
+    // ***** 888poker Hand History for Game 1234567890 *****
+    let title = lines.next()?.trim_start_matches('*').trim_start();
+    let site = title.split(' ').next()?;
+    let hand = title[title.find(" for ")? + " for ".len()..].split(' ').next()?;
+
+    // $0.25/$0.50 Blinds No Limit Holdem - *** 08 02 2020 04:01:19
+    let game = lines.next()?;
+    let (stakes, rest) = game.split_once(' ')?;
+    let (buy_in_min, buy_in_max) = parse_stakes(stakes)?;
+    let rest = rest.strip_prefix("Blinds ").unwrap_or(rest);
+    let game_type = &rest[..rest.find(" - ").unwrap_or(rest.len())];
+
+    // Table Sparta 6 Max (Real Money)
+    let table_line = lines.next()?;
+    let table_name = table_line.strip_prefix("Table ")?;
+    let table_name = &table_name[..table_name.find(" (").unwrap_or(table_name.len())];
+    let (table_name, max_players) = match table_name.strip_suffix(" Max").and_then(|t| t.rsplit_once(' ')) {
+        Some((name, max)) => (name, parse_integer(max).map_or(0, |(v, _)| v as u8)),
+        None => (table_name, 0),
+    };
+    let play_money = table_line.contains("(Play Money)");
+
+    let mut b = HandBuilder::default();
+    let mut button = 0;
+    while let Some(line) = lines.peek().copied() {
+        if let Some(seat) = parse_button(line) {
+            button = seat;
+        } else if !line.starts_with("Total number of players") && !b.seat(line, data) {
+            break;
+        }
+        lines.next();
+    }
+
+    let info = HandInfo{
+        site_name: "888poker", site: span(site), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(game_type), buy_in_min, buy_in_max,
+    };
+    let table = Table{name: span(table_name), max_players, button, play_money};
+    let header = Header{info, table, players: PlayerVec::new(), actions: ActionVec::new()};
+
+    for line in lines {
+        if let Some(street) = line.strip_prefix("** Dealing ") {
+            // ** Dealing down cards **, ** Dealing flop ** [ 8d, 3c, Ad ]
+            let t = match street.split(' ').next()? {
+                "flop" => StreetType::Flop,
+                "turn" => StreetType::Turn,
+                "river" => StreetType::River,
+                _ => { b.hole_cards(); continue; }
+            };
+            b.deal_board(t, parse_cards(line.rbetween('[', ']')));
+        }
+        else if let Some((name, rest)) = b.player(line, data) {
+            parse_action(&mut b, span(name), name, rest);
+        }
+        else {
+            b.dealt(line, data);
+        }
+    }
+
+    Some(b.finish(header, None, data))
+}
+
+fn parse_action<'a>(b: &mut HandBuilder<'a>, span: Span, name: &'a str, rest: &str) {
+    let amount = || parse_currency_any(rest.between('[', ']'));
+    if rest.starts_with("posts ") {
+        let t = if rest.starts_with("posts dead") {
+            BlindType::SmallAndBig
+        } else if rest.starts_with("posts small") {
+            BlindType::Small
+        } else if rest.starts_with("posts ante") {
+            BlindType::Ante
+        } else {
+            BlindType::Big
+        };
+        if let Some(amount) = amount() { b.blind(name, span, t, amount); }
+    }
+    else if rest.starts_with("calls ") || rest.starts_with("bets ") || rest.starts_with("raises ") || rest.starts_with("all-in ") {
+        if let Some(amount) = amount() { b.play(name, span, amount, rest.starts_with("all-in")); }
+    }
+    else if let Some(won) = rest.strip_prefix("collected ") {
+        // collected [ $2.85 ]
+        if let Some(amount) = parse_currency_any(won.between('[', ']')) {
+            b.push(Action::CollectedPot(Pot::Main, span, amount, None));
+        }
+    }
+    else {
+        b.common_action(span, rest);
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_888_hand() {
+        let data = "#Game No : 1234567890
+***** 888poker Hand History for Game 1234567890 *****
+$0.25/$0.50 Blinds No Limit Holdem - *** 08 02 2020 04:01:19
+Table Sparta 6 Max (Real Money)
+Seat 3 is the button
+Total number of players : 3
+Seat 1: pondtree312 ( $49.50 )
+Seat 2: Rifama ( $50 )
+Seat 3: spr1teg ( $50.50 )
+pondtree312 posts small blind [$0.25]
+Rifama posts big blind [$0.50]
+** Dealing down cards **
+Dealt to spr1teg [ 8s, Jd ]
+spr1teg raises [$1.50]
+pondtree312 folds
+Rifama calls [$1]
+** Dealing flop ** [ 8d, 3c, Ad ]
+Rifama checks
+spr1teg bets [$2]
+Rifama folds
+** Summary **
+spr1teg did not show his hand
+spr1teg collected [ $3.25 ]
+
+#Game No : 1234567891
+***** 888poker Hand History for Game 1234567891 *****
+";
+        assert!(detect_site(data).is_some_and(|site| site.name() == "888poker"));
+        assert_eq!(Poker888.count_hands(data), 2);
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.info.site], "888poker");
+        assert_eq!(&data[hand.header.info.id], "1234567890");
+        assert_eq!(&data[hand.header.info.game_type], "No Limit Holdem");
+        assert_eq!(&data[hand.header.table.name], "Sparta");
+        assert_eq!(hand.header.table.max_players, 6);
+        assert_eq!(hand.header.table.button, 3);
+        assert_eq!(hand.hole_cards.dealt_to[0].1.len(), 2);
+
+        assert!(matches!(hand.hole_cards.actions[0], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, ..}), ..})) if amount == 1.5));
+        assert!(matches!(hand.hole_cards.actions[2], Action::Play(Play::Call, _, _)));
+        assert_eq!(hand.streets.len(), 1);
+        assert!(matches!(hand.streets[0].actions[1], Action::Play(Play::Bet, _, _)));
+        assert!(matches!(hand.streets[0].actions[4], Action::CollectedPot(Pot::Main, _, Currency{amount, ..}, None) if amount == 3.25));
+        assert_eq!(hand.summary.pot.amount, 3.25);
+        assert_eq!(hand.summary.seats[0].seat, 3);
+    }
+}
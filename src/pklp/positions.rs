@@ -0,0 +1,191 @@
+//region Positions
+
+// Positions of the players dealt in, worked out from the button and the blinds that were posted:
+//
+// 6-max    UTG HJ CO BTN SB BB
+// 9-max    UTG UTG+1 UTG+2 LJ HJ CO BTN SB BB
+// heads-up BTN BB, the button posts the small blind
+//
+// A dead button on an empty seat leaves the hand without a BTN. Players sitting out are not dealt in,
+// players posting a big blind to come in early keep the position of their seat.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    SmallBlind,
+    BigBlind,
+    // Under the gun plus n
+    Utg(u8),
+    Lojack,
+    Hijack,
+    Cutoff,
+    Button,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Position::SmallBlind => write!(f, "SB"),
+            Position::BigBlind => write!(f, "BB"),
+            Position::Utg(0) => write!(f, "UTG"),
+            Position::Utg(n) => write!(f, "UTG+{}", n),
+            Position::Lojack => write!(f, "LJ"),
+            Position::Hijack => write!(f, "HJ"),
+            Position::Cutoff => write!(f, "CO"),
+            Position::Button => write!(f, "BTN"),
+        }
+    }
+}
+
+impl Json for Position {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // Players dealt in from the seat after the button, stud hands have no button and no positions
+    pub fn positions(&self, data: &str) -> Vec<(Span, Position)> {
+        let button = self.header.table.button;
+        if button == 0 { return Vec::new(); }
+
+        let acts = |name: &str| self.actions().any(|a| match a {
+            Action::Blind(_, n, _) | Action::Play(_, n, _) | Action::Fold(n, _) => &data[*n] == name,
+            _ => false,
+        });
+        let sits_out = |name: &str| self.header.actions.iter().any(|a| matches!(a, Action::Event(Event::Sitout, n) if &data[*n] == name));
+        let mut order = self.players_after_button().into_iter()
+            .filter(|p| acts(&data[p.name]) && !sits_out(&data[p.name]))
+            .collect::<Vec<_>>();
+
+        let mut positions = Vec::new();
+        if order.last().is_some_and(|p| p.seat == button) {
+            let p = order.pop().unwrap();
+            positions.push((p.name, Position::Button));
+        }
+        let posted = |p: &Player, t: fn(&BlindType) -> bool| self.header.actions.iter()
+            .any(|a| matches!(a, Action::Blind(b, n, _) if t(b) && data[*n] == data[p.name]));
+
+        // Heads-up the button posted the small blind, the seat after it starts with the big blind
+        let mut rest = order.as_slice();
+        if let Some((p, r)) = rest.split_first().filter(|(p, _)| posted(p, |b| matches!(b, BlindType::Small))) {
+            positions.push((p.name, Position::SmallBlind));
+            rest = r;
+        }
+        if let Some((p, r)) = rest.split_first().filter(|(p, _)| posted(p, |b| matches!(b, BlindType::Big))) {
+            positions.push((p.name, Position::BigBlind));
+            rest = r;
+        }
+
+        // The first to act is under the gun, the last three before the button are LJ, HJ and CO
+        let late = rest.len().saturating_sub(1).min(3);
+        let early = rest.len() - late;
+        for (i, p) in rest.iter().enumerate() {
+            let position = if i < early {
+                Position::Utg(i as u8)
+            } else {
+                [Position::Lojack, Position::Hijack, Position::Cutoff][3 - (rest.len() - i)]
+            };
+            positions.push((p.name, position));
+        }
+
+        let seat = |name: &Span| self.header.players.iter().find(|p| p.name == *name).map_or(0, |p| p.seat);
+        positions.sort_by_key(|(name, _)| seat(name));
+        positions
+    }
+
+    pub fn position(&self, name: &str, data: &str) -> Option<Position> {
+        self.positions(data).into_iter().find(|(n, _)| &data[*n] == name).map(|(_, p)| p)
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_sizes() {
+        let positions = |data: &str| parse_string(data)[0].positions(data).iter().map(|(name, p)| format!("{} {}", &data[*name], p)).collect::<Vec<_>>();
+        let full = "PokerStars Hand #1:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 9-max Seat #2 is the button
+Seat 1: p1 ($50 in chips)
+Seat 2: p2 ($50 in chips)
+Seat 3: p3 ($50 in chips)
+Seat 4: p4 ($50 in chips)
+Seat 5: p5 ($50 in chips)
+Seat 6: p6 ($50 in chips)
+Seat 7: p7 ($50 in chips)
+Seat 8: p8 ($50 in chips)
+Seat 9: p9 ($50 in chips)
+p3: posts small blind $0.25
+p4: posts big blind $0.50
+*** HOLE CARDS ***
+p5: folds
+p6: folds
+p7: folds
+p8: folds
+p9: folds
+p1: folds
+p2: folds
+p3: folds
+Uncalled bet ($0.25) returned to p4
+p4 collected $0.50 from pot
+*** SUMMARY ***
+Total pot $0.50 | Rake $0
+";
+        assert_eq!(positions(full), ["p1 CO", "p2 BTN", "p3 SB", "p4 BB", "p5 UTG", "p6 UTG+1", "p7 UTG+2", "p8 LJ", "p9 HJ"]);
+
+        let heads_up = "PokerStars Hand #2:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($50 in chips)
+Seat 4: p4 ($50 in chips)
+p4: posts small blind $0.25
+p1: posts big blind $0.50
+*** HOLE CARDS ***
+p4: folds
+Uncalled bet ($0.25) returned to p1
+p1 collected $0.50 from pot
+*** SUMMARY ***
+Total pot $0.50 | Rake $0
+";
+        assert_eq!(positions(heads_up), ["p1 BB", "p4 BTN"]);
+    }
+
+    #[test]
+    fn dead_button_and_sitting_out() {
+        let positions = |data: &str| parse_string(data)[0].positions(data).iter().map(|(name, p)| format!("{} {}", &data[*name], p)).collect::<Vec<_>>();
+        // The button moved to the empty seat 3, p5 sits out and p6 posts to come in early
+        let data = "PokerStars Hand #3:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: p1 ($50 in chips)
+Seat 2: p2 ($50 in chips)
+Seat 4: p4 ($50 in chips)
+Seat 5: p5 ($50 in chips)
+Seat 6: p6 ($50 in chips)
+p4: posts big blind $0.50
+p6: posts big blind $0.50
+p5: sits out
+*** HOLE CARDS ***
+p6: checks
+p1: folds
+p2: folds
+p4: checks
+*** FLOP *** [8d 3c Ad]
+p4: checks
+p6: checks
+*** SUMMARY ***
+Total pot $1 | Rake $0
+";
+        assert_eq!(positions(data), ["p1 HJ", "p2 CO", "p4 BB", "p6 UTG"]);
+        let hands = parse_string(data);
+        assert_eq!(hands[0].position("p2", data), Some(Position::Cutoff));
+        assert_eq!(hands[0].position("p5", data), None);
+        assert!(to_json_with(&hands, data, JsonOptions{positions: true, ..Default::default()}).unwrap()
+            .contains(r#""positions":[["p1","HJ"],["p2","CO"],["p4","BB"],["p6","UTG"]]"#));
+    }
+}
@@ -0,0 +1,207 @@
+//region Pots
+
+// Pots rebuilt from the betting, every all-in caps a pot shared by the players who put in at least as much:
+//
+// p1 all-in for 10, p2 all-in for 30, p3 calls 30, p4 folds after putting in 5
+// Main pot 35 for p1 p2 p3, Side pot-1 40 for p2 p3
+//
+// Amounts include the rake, PokerStars lists the pots of the summary after the rake is taken.
+// The layers are in order, the main pot first and then side pot-1, side pot-2.
+
+use super::*;
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct PotLayer {
+    pub pot: Pot,
+    pub amount: Currency,
+    pub eligible: Vec<Span>,
+    // CollectedPot actions naming this pot
+    pub collected: Vec<(Span, Currency)>,
+}
+
+#[allow(dead_code)]
+impl Hand {
+    pub fn pots(&self, data: &str) -> Vec<PotLayer> {
+        let symbol = self.summary.pot.symbol;
+        let mut state = HandState::new(self, data);
+        state.by_ref().for_each(drop);
+
+        let span = |name: &str| self.header.players.iter().find(|p| &data[p.name] == name).map(|p| p.name);
+        let mut caps = state.players.iter().filter(|p| p.all_in && !p.folded).map(|p| p.invested).collect::<Vec<_>>();
+        caps.sort_by(Float::total_cmp);
+        caps.dedup();
+        caps.push(Float::INFINITY);
+
+        let mut layers: Vec<PotLayer> = Vec::new();
+        let mut previous = 0.0;
+        for cap in caps {
+            let amount = state.players.iter().map(|p| p.invested.min(cap) - p.invested.min(previous)).sum::<Float>();
+            let eligible = state.players.iter()
+                .filter(|p| !p.folded && p.invested > previous)
+                .filter_map(|p| span(p.name))
+                .collect::<Vec<_>>();
+            previous = cap;
+            if amount <= 0.005 { continue; }
+            match layers.last_mut() {
+                // Chips nobody left in the hand matched go to the pot below
+                Some(last) if eligible.is_empty() => last.amount.amount += amount,
+                _ => {
+                    let pot = if layers.is_empty() { Pot::Main } else { Pot::Side(Some(layers.len() as u8)) };
+                    layers.push(PotLayer{pot, amount: Currency{symbol, amount}, eligible, collected: Vec::new()});
+                }
+            }
+        }
+
+        for a in self.actions() {
+            if let Action::CollectedPot(pot, name, amount, _) = a {
+                let i = match pot {
+                    Pot::Main => Some(0).filter(|_| !layers.is_empty()),
+                    Pot::Side(Some(n)) if (*n as usize) < layers.len() => Some(*n as usize),
+                    // A side pot the log does not number goes to the last one the player can win with chips left in it
+                    Pot::Side(_) => layers.iter().rposition(|l| {
+                        let left = l.amount.amount - l.collected.iter().map(|c| c.1.amount).sum::<Float>();
+                        matches!(l.pot, Pot::Side(_)) && l.eligible.iter().any(|e| data[*e] == data[*name]) && left > amount.amount - 0.005
+                    }),
+                };
+                if let Some(i) = i {
+                    layers[i].collected.push((*name, *amount));
+                }
+            }
+        }
+        layers
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_way_all_in() {
+        let data = "PokerStars Hand #208966141600:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($10 in chips)
+Seat 2: p2 ($30 in chips)
+Seat 3: p3 ($50 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $4.50 to $5
+p4: folds
+p1: raises $4.75 to $10 and is all-in
+p2: raises $20 to $30 and is all-in
+p3: calls $25
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [8s 8h] (three of a kind, Eights)
+p3 collected $39 from side pot
+p1 collected $29 from main pot
+*** SUMMARY ***
+Total pot $70 Main pot $29. Side pot $39. | Rake $2
+";
+        let hands = parse_string(data);
+        let summary = &hands[0].summary;
+        assert_eq!(summary.side_pots.len(), 1);
+        assert_eq!(summary.side_pot.amount, 39.0);
+
+        let pots = hands[0].pots(data);
+        assert_eq!(pots.len(), 2);
+        assert!(matches!(pots[0].pot, Pot::Main));
+        assert_eq!(pots[0].amount.amount, 30.0);
+        assert_eq!(pots[0].eligible.iter().map(|s| &data[*s]).collect::<Vec<_>>(), ["p1", "p2", "p3"]);
+        assert_eq!(&data[pots[0].collected[0].0], "p1");
+        assert!(matches!(pots[1].pot, Pot::Side(Some(1))));
+        assert_eq!(pots[1].amount.amount, 40.0);
+        assert_eq!(pots[1].eligible.iter().map(|s| &data[*s]).collect::<Vec<_>>(), ["p2", "p3"]);
+        assert_eq!(pots[1].collected[0].1.amount, 39.0);
+    }
+
+    #[test]
+    fn numbered_side_pots() {
+        let data = "PokerStars Hand #208966141601:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($5 in chips)
+Seat 2: p2 ($10 in chips)
+Seat 3: p3 ($20 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $19.50 to $20 and is all-in
+p4: calls $20
+p1: calls $4.75 and is all-in
+p2: calls $9.50 and is all-in
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [Qh Qc] (a pair of Queens)
+p4: shows [2h 7c] (high card Ace)
+p3 collected $20 from side pot-2
+p2 collected $15 from side pot-1
+p1 collected $18 from main pot
+*** SUMMARY ***
+Total pot $55 Main pot $18. Side pot-1 $15. Side pot-2 $20. | Rake $2
+";
+        let hands = parse_string(data);
+        let summary = &hands[0].summary;
+        assert_eq!(summary.side_pots.iter().map(|s| s.amount).collect::<Vec<_>>(), [15.0, 20.0]);
+        assert_eq!(summary.side_pot.amount, 35.0);
+
+        let pots = hands[0].pots(data);
+        assert_eq!(pots.iter().map(|p| (matches!(p.pot, Pot::Main), p.amount.amount, p.eligible.len())).collect::<Vec<_>>(),
+            [(true, 20.0, 4), (false, 15.0, 3), (false, 20.0, 2)]);
+        let collected = |i: usize| pots[i].collected.iter().map(|c| &data[c.0]).collect::<Vec<_>>();
+        assert_eq!(collected(0), ["p1"]);
+        assert_eq!(collected(1), ["p2"]);
+        assert_eq!(collected(2), ["p3"]);
+    }
+
+    #[test]
+    fn lower_side_pot_first() {
+        // p3 can win side pot-2 and collects side pot-1 first, its amount also fits in side pot-2
+        let data = "PokerStars Hand #208966141602:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($5 in chips)
+Seat 2: p2 ($10 in chips)
+Seat 3: p3 ($20 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $19.50 to $20 and is all-in
+p4: calls $20
+p1: calls $4.75 and is all-in
+p2: calls $9.50 and is all-in
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [Qh Qc] (a pair of Queens)
+p4: shows [Jh Jc] (a pair of Jacks)
+p3 collected $14 from side pot-1
+p3 collected $20 from side pot-2
+p1 collected $19 from main pot
+*** SUMMARY ***
+Total pot $55 Main pot $19. Side pot-1 $14. Side pot-2 $20. | Rake $2
+";
+        let hands = parse_string(data);
+        assert!(matches!(hands[0].streets[3].actions[4], Action::CollectedPot(Pot::Side(Some(1)), _, _, _)));
+        let pots = hands[0].pots(data);
+        assert_eq!(pots[1].collected.iter().map(|c| c.1.amount).collect::<Vec<_>>(), [14.0]);
+        assert_eq!(pots[2].collected.iter().map(|c| c.1.amount).collect::<Vec<_>>(), [20.0]);
+    }
+}
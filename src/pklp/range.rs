@@ -0,0 +1,282 @@
+//region Range
+
+// Sets of two card hands in the usual range notation, parts are separated by commas:
+//
+// AKs        the 4 suited combos     AKo   the 12 offsuit combos     AK    all 16
+// 77+        sevens up to aces       ATo+  ATo AJo AQo AKo           KQs-K9s  KQs KJs KTs K9s
+// AhKh       one combo               random   all 1326 combos
+// AKs:0.5    half of the combos, later parts replace the weights of earlier ones
+
+use super::*;
+
+const SUITS: [Suit; 4] = [Suit::Spade, Suit::Club, Suit::Heart, Suit::Diamond];
+
+#[derive(Debug, Clone)]
+pub struct Range {
+    // Indexed by the two cards, the lower card index first
+    weights: Vec<Float>,
+}
+
+// Ranks of a hand class, aces are 14, the suits are None for pairs and hands written without s or o
+#[derive(Clone, Copy, PartialEq)]
+struct Class {
+    high: u8,
+    low: u8,
+    suited: Option<bool>,
+}
+
+fn card_index(card: &Card) -> usize {
+    (card.n as usize - 1) * 4 + card.suit as usize
+}
+
+fn card(rank: u8, suit: usize) -> Card {
+    Card{n: if rank == 14 { 1 } else { rank }, suit: SUITS[suit]}
+}
+
+fn parse_rank(c: char) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        'A' => Some(14),
+        'K' => Some(13),
+        'Q' => Some(12),
+        'J' => Some(11),
+        'T' => Some(10),
+        c @ '2'..='9' => Some(c as u8 - b'0'),
+        _ => None,
+    }
+}
+
+fn parse_class(part: &str) -> Option<Class> {
+    let mut chars = part.chars();
+    let (a, b) = (parse_rank(chars.next()?)?, parse_rank(chars.next()?)?);
+    let suited = match chars.next() {
+        None => None,
+        Some('s' | 'S') if a != b => Some(true),
+        Some('o' | 'O') if a != b => Some(false),
+        _ => return None,
+    };
+    if chars.next().is_some() { return None; }
+    Some(Class{high: a.max(b), low: a.min(b), suited})
+}
+
+#[allow(dead_code)]
+impl Range {
+    pub fn random() -> Self {
+        let mut range = Range{weights: vec![0.0; 52 * 52]};
+        for a in 0..52 {
+            for b in a + 1..52 {
+                range.weights[a * 52 + b] = 1.0;
+            }
+        }
+        range
+    }
+
+    // None when a part is not valid notation
+    pub fn parse(notation: &str) -> Option<Self> {
+        let mut range = Range{weights: vec![0.0; 52 * 52]};
+        for part in notation.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (part, weight) = match part.split_once(':') {
+                Some((part, weight)) => (part.trim(), weight.trim().parse::<Float>().ok().filter(|w| (0.0..=1.0).contains(w))?),
+                None => (part, 1.0),
+            };
+
+            if part.eq_ignore_ascii_case("random") {
+                range.weights.iter_mut().zip(Range::random().weights).filter(|(_, r)| *r > 0.0).for_each(|(w, _)| *w = weight);
+                continue;
+            }
+            if let Some([a, b]) = parse_combo(part) {
+                range.set(&a, &b, weight);
+                continue;
+            }
+
+            let classes = if let Some((first, last)) = part.split_once('-') {
+                // KQs-K9s, 77-44
+                let (first, last) = (parse_class(first)?, parse_class(last)?);
+                let (top, bottom) = if first.low >= last.low { (first, last) } else { (last, first) };
+                if top.suited != bottom.suited { return None; }
+                if top.high == top.low && bottom.high == bottom.low {
+                    (bottom.high..=top.high).map(|r| Class{high: r, low: r, suited: None}).collect()
+                } else if top.high == bottom.high && top.high != top.low && bottom.high != bottom.low {
+                    (bottom.low..=top.low).map(|low| Class{low, ..top}).collect()
+                } else {
+                    return None;
+                }
+            } else if let Some(base) = part.strip_suffix('+') {
+                // 77+ up to aces, ATo+ up to the kicker below the first card
+                let base = parse_class(base)?;
+                if base.high == base.low {
+                    (base.high..=14).map(|r| Class{high: r, low: r, suited: None}).collect()
+                } else {
+                    (base.low..base.high).map(|low| Class{low, ..base}).collect()
+                }
+            } else {
+                vec![parse_class(part)?]
+            };
+
+            for class in classes {
+                for (a, b) in (0..4).flat_map(|a| (0..4).map(move |b| (a, b))) {
+                    let pair = class.high == class.low;
+                    let keep = match class.suited {
+                        _ if pair => a < b,
+                        Some(true) => a == b,
+                        Some(false) => a != b,
+                        None => true,
+                    };
+                    if keep {
+                        range.set(&card(class.high, a), &card(class.low, b), weight);
+                    }
+                }
+            }
+        }
+        Some(range)
+    }
+
+    fn set(&mut self, a: &Card, b: &Card, weight: Float) {
+        let (a, b) = (card_index(a), card_index(b));
+        if a != b {
+            self.weights[a.min(b) * 52 + a.max(b)] = weight;
+        }
+    }
+
+    // 0 for anything but two different cards out of the range
+    pub fn weight(&self, cards: &[Card]) -> Float {
+        match cards {
+            [a, b] => {
+                let (a, b) = (card_index(a), card_index(b));
+                if a == b { 0.0 } else { self.weights[a.min(b) * 52 + a.max(b)] }
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn contains(&self, cards: &[Card]) -> bool {
+        self.weight(cards) > 0.0
+    }
+
+    pub fn combos(&self) -> impl Iterator<Item=([Card; 2], Float)> + '_ {
+        self.weights.iter().enumerate().filter(|(_, w)| **w > 0.0).map(|(i, w)| {
+            let card = |i: usize| Card{n: (i / 4) as u8 + 1, suit: SUITS[i % 4]};
+            ([card(i / 52), card(i % 52)], *w)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.iter().filter(|w| **w > 0.0).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// AhKh
+fn parse_combo(part: &str) -> Option<[Card; 2]> {
+    let b = part.as_bytes();
+    if b.len() != 4 { return None; }
+    let card = |c: &[u8]| {
+        // In the order of SUITS
+        let suit = "schd".find(c[1].to_ascii_lowercase() as char)?;
+        Some(card(parse_rank(c[0] as char)?, suit))
+    };
+    Some([card(&b[..2])?, card(&b[2..])?])
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // None when the cards of the player were neither dealt to the hero nor shown
+    pub fn in_range(&self, name: &str, range: &Range, data: &str) -> Option<bool> {
+        self.known_hole_cards(data).get(name).map(|cards| range.contains(cards))
+    }
+
+    // Players with known cards in the range, in seat order
+    pub fn players_in_range<'d>(&self, range: &Range, data: &'d str) -> Vec<&'d str> {
+        let known = self.known_hole_cards(data);
+        self.header.players.iter()
+            .map(|p| &data[p.name])
+            .filter(|name| known.get(name).is_some_and(|cards| range.contains(cards)))
+            .collect()
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation() {
+        let len = |s: &str| Range::parse(s).map(|r| r.len());
+        assert_eq!(len("AKs"), Some(4));
+        assert_eq!(len("AKo"), Some(12));
+        assert_eq!(len("KA"), Some(16));
+        assert_eq!(len("77"), Some(6));
+        assert_eq!(len("77+"), Some(48));
+        assert_eq!(len("ATo+"), Some(48));
+        assert_eq!(len("A2s+"), Some(48));
+        assert_eq!(len("KQs-K9s"), Some(16));
+        assert_eq!(len("44-77"), Some(24));
+        assert_eq!(len("random"), Some(1326));
+        assert_eq!(len("AhKh, QQ+, AKs"), Some(22));
+        assert_eq!(len(""), Some(0));
+        for invalid in ["AXs", "AAs", "AKs-QJs", "KQs-K9o", "AKs:2", "AhKx"] {
+            assert!(Range::parse(invalid).is_none(), "{}", invalid);
+        }
+
+        let range = Range::parse("ATo+, KQs-K9s:0.5, 99:0.25, random:0, AsKs:0.75").unwrap();
+        assert_eq!(range.len(), 1);
+        let range = Range::parse("ATo+, KQs-K9s:0.5, 99:0.25, AsKs:0.75").unwrap();
+        assert_eq!(range.weight(&parse_cards("Kd Td")), 0.5);
+        assert_eq!(range.weight(&parse_cards("9c 9h")), 0.25);
+        assert_eq!(range.weight(&parse_cards("Ks As")), 0.75);
+        assert_eq!(range.weight(&parse_cards("Ad Jc")), 1.0);
+        assert!(!range.contains(&parse_cards("Ad Jd")));
+        assert!(!range.contains(&parse_cards("Ad Jc 2c")));
+        assert_eq!(range.combos().map(|(_, w)| w).sum::<Float>(), 48.0 + 16.0 * 0.5 + 6.0 * 0.25 + 0.75);
+        assert!(range.combos().all(|(cards, w)| range.weight(&cards) == w));
+    }
+
+    #[test]
+    fn known_cards_in_range() {
+        let data = "PokerStars Hand #208966141604:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: pondtree312 ($50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+Dealt to Rifama [Qh Qd]
+spr1teg: raises $1 to $1.50
+pondtree312: folds
+Rifama: calls $1
+*** FLOP *** [8d 3c Ad]
+Rifama: checks
+spr1teg: checks
+*** TURN *** [8d 3c Ad] [9c]
+Rifama: checks
+spr1teg: checks
+*** RIVER *** [8d 3c Ad 9c] [6d]
+Rifama: checks
+spr1teg: checks
+*** SHOW DOWN ***
+Rifama: shows [Qh Qd] (a pair of Queens)
+spr1teg: shows [Ts 9s] (a pair of Nines)
+Rifama collected $3.25 from pot
+*** SUMMARY ***
+Total pot $3.25 | Rake $0
+";
+        let hands = parse_string(data);
+        let range = Range::parse("QQ+, T9s").unwrap();
+        assert_eq!(hands[0].players_in_range(&range, data), ["Rifama", "spr1teg"]);
+        assert_eq!(hands[0].in_range("spr1teg", &Range::parse("JJ+").unwrap(), data), Some(false));
+        assert_eq!(hands[0].in_range("pondtree312", &range, data), None);
+
+        let data = std::fs::read_to_string("data/example/pluribus_example.txt").unwrap();
+        let data = data.as_str();
+        let random = Range::random();
+        for hand in parse_string(data) {
+            assert_eq!(hand.players_in_range(&random, data).len(), hand.hole_cards.dealt_to.len());
+        }
+    }
+}
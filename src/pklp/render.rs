@@ -0,0 +1,564 @@
+//region Render
+
+// Writes hands back in the PokerStars grammar, whatever site they were read from:
+//
+// PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50)
+// Table 'Acamar V' 6-max Seat #3 is the button
+// Seat 1: pondtree312 ($49.50 in chips)
+// pondtree312: posts small blind $0.25
+// *** HOLE CARDS ***
+// spr1teg: raises $0.75 to $1.25
+// *** SUMMARY ***
+// Total pot $3 | Rake $0.15
+// Seat 3: spr1teg (button) showed [8s Jd] and won ($2.85) with a pair of Eights
+//
+// Only what the model captures is written, the date and the hand descriptions of the showdown are lost.
+
+use super::*;
+use std::fmt::Write;
+
+#[allow(dead_code)]
+pub fn render(hands: &[Hand], data: &str) -> String {
+    hands.iter().map(|hand| render_hand(hand, data)).collect::<Vec<_>>().join("\n\n")
+}
+
+#[allow(dead_code)]
+pub fn render_hand(hand: &Hand, data: &str) -> String {
+    let mut out = String::new();
+    render_header(&mut out, &hand.header, data);
+
+    // Stud hands deal every card on the streets and have no hole cards section
+    let hole_cards = &hand.hole_cards;
+    if !hole_cards.dealt_to.is_empty() || !hole_cards.actions.is_empty() {
+        let is_draw = hand.streets.iter().any(|s| matches!(s.t, StreetType::Draw));
+        out.push_str(if is_draw { "*** DEALING HANDS ***\n" } else { "*** HOLE CARDS ***\n" });
+        for (name, cards) in &hole_cards.dealt_to {
+            writeln!(out, "Dealt to {} [{}]", &data[*name], cards_str(cards)).unwrap();
+        }
+        render_actions(&mut out, &hole_cards.actions, Some(hand), data);
+    }
+
+    let draws = hand.streets.iter().filter(|s| matches!(s.t, StreetType::Draw)).count();
+    for street in &hand.streets {
+        render_street(&mut out, street, draws, hand, data);
+    }
+
+    render_summary(&mut out, hand, data);
+    out
+}
+
+fn render_header(out: &mut String, header: &Header, data: &str) {
+    // PokerStars Home Game Hand #208966141596: {Club Name}  Hold'em No Limit (10/20)
+    // PokerStars Hand #208966141598:  HORSE (Razz Limit, $0.04/$0.08)
+    let info = &header.info;
+    out.push_str("PokerStars");
+    if info.is_zoom { out.push_str(" Zoom"); }
+    if info.home_game_club.is_some() { out.push_str(" Home Game"); }
+    write!(out, " Hand #{}: ", &data[info.id]).unwrap();
+    if let Some(club) = info.home_game_club {
+        write!(out, "{{{}}} ", &data[club]).unwrap();
+    }
+    let stakes = format!("{}/{}", amount_str(info.buy_in_min), amount_str(info.buy_in_max));
+    match info.mixed_game {
+        Some(mixed) => writeln!(out, " {} ({}, {})", &data[mixed], &data[info.game_type], stakes).unwrap(),
+        None => writeln!(out, " {} ({})", &data[info.game_type], stakes).unwrap(),
+    }
+
+    // Table 'Aase III' 9-max (Play Money) Seat #5 is the button
+    let table = &header.table;
+    write!(out, "Table '{}'", &data[table.name]).unwrap();
+    if table.max_players > 0 { write!(out, " {}-max", table.max_players).unwrap(); }
+    if table.play_money { out.push_str(" (Play Money)"); }
+    if table.button > 0 { write!(out, " Seat #{} is the button", table.button).unwrap(); }
+    out.push('\n');
+
+    for p in &header.players {
+        write!(out, "Seat {}: {} ({} in chips", p.seat, &data[p.name], amount_str(p.chips)).unwrap();
+        if let Some(bounty) = p.bounty {
+            write!(out, ", {} bounty", amount_str(bounty)).unwrap();
+        }
+        out.push_str(")\n");
+    }
+
+    render_actions(out, &header.actions, None, data);
+}
+
+fn render_street(out: &mut String, street: &Street, draws: usize, hand: &Hand, data: &str) {
+    const ORDINALS: [&str; 4] = ["FIRST", "SECOND", "THIRD", "FOURTH"];
+    let ordinal = ORDINALS.get(street.index as usize).copied().unwrap_or("FOURTH");
+    let name = match street.t {
+        StreetType::Flop => "FLOP",
+        StreetType::Turn => "TURN",
+        StreetType::River => "RIVER",
+        StreetType::Draw => "DRAW",
+        StreetType::Third => "3rd STREET",
+        StreetType::Fourth => "4th STREET",
+        StreetType::Fifth => "5th STREET",
+        StreetType::Sixth => "6th STREET",
+        StreetType::Seventh => "7th STREET",
+        StreetType::Showdown => "SHOW DOWN",
+    };
+
+    // *** FLOP ***, *** SECOND FLOP *** of a hand run twice, *** FIRST DRAW *** of a triple draw
+    let numbered = street.index > 0 || (matches!(street.t, StreetType::Draw) && draws > 1);
+    if numbered {
+        write!(out, "*** {} {} ***", ordinal, name).unwrap();
+    } else {
+        write!(out, "*** {} ***", name).unwrap();
+    }
+    if let Some(cards) = &street.cards {
+        write!(out, " [{}]", cards_str(cards)).unwrap();
+    }
+    if let Some(card) = street.new_card {
+        write!(out, " [{}]", card).unwrap();
+    }
+    out.push('\n');
+
+    let dealt_to = |out: &mut String, (name, cards, new_cards): &(Span, Cards, Option<Cards>)| {
+        write!(out, "Dealt to {} [{}]", &data[*name], cards_str(cards)).unwrap();
+        if let Some(new_cards) = new_cards {
+            write!(out, " [{}]", cards_str(new_cards)).unwrap();
+        }
+        out.push('\n');
+    };
+
+    if matches!(street.t, StreetType::Draw) {
+        // Replacement cards are dealt right after the discards of the player
+        let mut written = vec![false; street.dealt_to.len()];
+        for a in &street.actions {
+            render_action(out, a, Some(hand), data);
+            if let Action::Discard(name, ..) | Action::Play(Play::Stand, name, _) = a {
+                if let Some(i) = street.dealt_to.iter().position(|d| data[d.0] == data[*name]).filter(|i| !written[*i]) {
+                    dealt_to(out, &street.dealt_to[i]);
+                    written[i] = true;
+                }
+            }
+        }
+        for (d, _) in street.dealt_to.iter().zip(written).filter(|(_, w)| !w) {
+            dealt_to(out, d);
+        }
+    } else {
+        for d in &street.dealt_to {
+            dealt_to(out, d);
+        }
+        render_actions(out, &street.actions, Some(hand), data);
+    }
+}
+
+fn render_actions(out: &mut String, actions: &[Action], hand: Option<&Hand>, data: &str) {
+    // toyochan: completes it to $0.04, raises by the difference to the bring in
+    let mut bring_in = None;
+    for a in actions {
+        match a {
+            Action::Blind(BlindType::BringIn, _, amount) => bring_in = Some(amount.amount),
+            Action::Play(Play::Raise, name, Some(d)) if bring_in.is_some_and(|b| d.bet_to.is_some_and(|to| (d.bet.amount + b - to.amount).abs() < 0.0001)) => {
+                write!(out, "{}: completes it to {}", &data[*name], amount_str(d.bet_to.unwrap())).unwrap();
+                if d.all_in { out.push_str(" and is all-in"); }
+                out.push('\n');
+                bring_in = None;
+                continue;
+            }
+            _ => {}
+        }
+        render_action(out, a, hand, data);
+    }
+}
+
+fn render_action(out: &mut String, action: &Action, hand: Option<&Hand>, data: &str) {
+    let play = |out: &mut String, name: &Span, verb: &str, d: &Option<ActionData>| {
+        write!(out, "{}: {}", &data[*name], verb).unwrap();
+        if let Some(d) = d {
+            write!(out, " {}", amount_str(d.bet)).unwrap();
+            if let Some(to) = d.bet_to {
+                write!(out, " to {}", amount_str(to)).unwrap();
+            }
+            if d.all_in { out.push_str(" and is all-in"); }
+        }
+    };
+
+    match action {
+        Action::Event(e, name) => {
+            let name = &data[*name];
+            match e {
+                Event::Connect => write!(out, "{} is connected", name),
+                Event::Disconnect => write!(out, "{} is disconnected", name),
+                Event::Timeout => write!(out, "{} has timed out", name),
+                Event::Leave => write!(out, "{} leaves the table", name),
+                Event::Sitout => write!(out, "{}: sits out", name),
+                Event::NotShow => write!(out, "{}: doesn't show hand", name),
+                // GGPoker only, there is no PokerStars line for it
+                Event::EvCashOut => return,
+            }.unwrap()
+        }
+        Action::Join(255, name) => write!(out, "{} will be allowed to play after the button", &data[*name]).unwrap(),
+        Action::Join(seat, name) => write!(out, "{} joins the table at seat #{}", &data[*name], seat).unwrap(),
+        Action::Play(t, name, d) => match t {
+            Play::Bet => play(out, name, "bets", d),
+            Play::Call => play(out, name, "calls", d),
+            Play::Raise => play(out, name, "raises", d),
+            Play::Check => write!(out, "{}: checks", &data[*name]).unwrap(),
+            Play::Stand => write!(out, "{}: stands pat", &data[*name]).unwrap(),
+            #[allow(deprecated)]
+            Play::Discard => write!(out, "{}: discards", &data[*name]).unwrap(),
+        },
+        Action::Blind(t, name, amount) => {
+            let blind = match t {
+                BlindType::Small => "posts small blind",
+                BlindType::Big => "posts big blind",
+                BlindType::SmallAndBig => "posts small & big blinds",
+                BlindType::Ante => "posts the ante",
+                BlindType::BringIn => "brings in for",
+            };
+            write!(out, "{}: {} {}", &data[*name], blind, amount_str(*amount)).unwrap()
+        }
+        Action::ShowHand(name, cards) => write!(out, "{}: shows [{}]", &data[*name], cards_str(cards)).unwrap(),
+        Action::Fold(name, None) => write!(out, "{}: folds", &data[*name]).unwrap(),
+        Action::Fold(name, Some(cards)) => write!(out, "{}: folds [{}]", &data[*name], cards_str(cards)).unwrap(),
+        Action::Discard(name, count, cards) => {
+            write!(out, "{}: discards {} card{}", &data[*name], count, if *count == 1 { "" } else { "s" }).unwrap();
+            if let Some(cards) = cards {
+                write!(out, " [{}]", cards_str(cards)).unwrap();
+            }
+        }
+        Action::Say(name, text) => write!(out, "{} said, \"{}\"", &data[*name], &data[*text]).unwrap(),
+        Action::CashOut(name, amount, fee) => {
+            write!(out, "{} cashed out the hand for {} | Cash Out Fee {}", &data[*name], amount_str(*amount), amount_str(*fee)).unwrap()
+        }
+        Action::PaysCashOutRisk(..) => return,
+        Action::UncalledBetReturned(name, amount) => write!(out, "Uncalled bet ({}) returned to {}", amount_str(*amount), &data[*name]).unwrap(),
+        Action::CollectedPot(pot, name, amount, _) => {
+            // The half of a hi/lo pot is only written in the summary
+            let side_pots = hand.map_or(0, |h| h.summary.side_pots.len());
+            let pot = match pot {
+                Pot::Side(Some(n)) if side_pots > 1 => format!("side pot-{}", n),
+                Pot::Side(_) => "side pot".to_string(),
+                Pot::Main if side_pots > 0 => "main pot".to_string(),
+                Pot::Main => "pot".to_string(),
+            };
+            write!(out, "{} collected {} from {}", &data[*name], amount_str(*amount), pot).unwrap()
+        }
+        Action::Unknown(line) => out.push_str(&data[*line]),
+    }
+    out.push('\n');
+}
+
+fn render_summary(out: &mut String, hand: &Hand, data: &str) {
+    // Total pot $20 Main pot $15. Side pot $5. | Rake $0 | Jackpot $0.10 | Bingo $0
+    let summary = &hand.summary;
+    out.push_str("*** SUMMARY ***\n");
+    write!(out, "Total pot {}", amount_str(summary.pot)).unwrap();
+    if summary.main_pot.amount != summary.pot.amount || !summary.side_pots.is_empty() {
+        write!(out, " Main pot {}.", amount_str(summary.main_pot)).unwrap();
+    }
+    match summary.side_pots.as_slice() {
+        [] => {}
+        [side_pot] => write!(out, " Side pot {}.", amount_str(*side_pot)).unwrap(),
+        side_pots => for (i, side_pot) in side_pots.iter().enumerate() {
+            write!(out, " Side pot-{} {}.", i + 1, amount_str(*side_pot)).unwrap();
+        },
+    }
+    if let Some(rake) = summary.rake {
+        write!(out, " | Rake {}", amount_str(rake)).unwrap();
+    }
+    if let Some(jackpot) = summary.jackpot {
+        write!(out, " | Jackpot {}", amount_str(jackpot)).unwrap();
+    }
+    for (name, amount) in &summary.fees {
+        write!(out, " | {} {}", &data[*name], amount_str(*amount)).unwrap();
+    }
+    out.push('\n');
+
+    match summary.boards.len() {
+        0 => {}
+        1 => writeln!(out, "Board [{}]", cards_str(&summary.boards[0])).unwrap(),
+        n => {
+            writeln!(out, "Hand was run {}", if n == 2 { "twice" } else { "three times" }).unwrap();
+            for (board, ordinal) in summary.boards.iter().zip(["FIRST", "SECOND", "THIRD", "FOURTH"]) {
+                writeln!(out, "{} Board [{}]", ordinal, cards_str(board)).unwrap();
+            }
+        }
+    }
+
+    let posted = |name: &str, t: fn(&BlindType) -> bool| hand.header.actions.iter()
+        .any(|a| matches!(a, Action::Blind(b, n, _) if t(b) && &data[*n] == name));
+    for seat in &summary.seats {
+        // Seat 3: spr1teg (button) showed [8s Jd] and won ($2.85) with a pair of Eights
+        let name = &data[seat.name];
+        write!(out, "Seat {}: {}", seat.seat, name).unwrap();
+        if seat.seat == hand.header.table.button { out.push_str(" (button)"); }
+        if posted(name, |b| matches!(b, BlindType::Small)) { out.push_str(" (small blind)"); }
+        if posted(name, |b| matches!(b, BlindType::Big)) { out.push_str(" (big blind)"); }
+
+        let verb = match &seat.cards {
+            Some(cards) if seat.mucked => { write!(out, " mucked [{}]", cards_str(cards)).unwrap(); None }
+            None if seat.mucked => { out.push_str(" mucked"); None }
+            Some(cards) => { write!(out, " showed [{}] and", cards_str(cards)).unwrap(); Some("won") }
+            None => Some("collected"),
+        };
+        match verb {
+            Some(_) if seat.won.is_empty() && seat.cards.is_some() => out.push_str(" lost"),
+            Some(_) if seat.won.is_empty() => out.push_str(&folded_str(hand, name, data)),
+            Some(verb) => for (i, award) in seat.won.iter().enumerate() {
+                if i > 0 { out.push_str(", and"); }
+                write!(out, " {} ({})", verb, amount_str(award.amount)).unwrap();
+                if let Some(h) = award.hand {
+                    write!(out, " with {}", &data[h]).unwrap();
+                }
+            },
+            None => {}
+        }
+        out.push('\n');
+    }
+}
+
+fn folded_str(hand: &Hand, name: &str, data: &str) -> String {
+    // folded before Flop (didn't bet), folded on the Turn, folded after the 2nd Draw
+    let by = |a: &Action| match a {
+        Action::Fold(n, _) | Action::Blind(_, n, _) | Action::Play(_, n, _) => data[*n] == *name,
+        _ => false,
+    };
+    let is_draw = hand.streets.iter().any(|s| matches!(s.t, StreetType::Draw));
+    let mut folded = String::from(if is_draw { " folded before the Draw" } else { " folded before Flop" });
+    for street in hand.streets.iter().filter(|s| s.actions.iter().any(|a| matches!(a, Action::Fold(..)) && by(a))) {
+        folded = match street.t {
+            StreetType::Flop => " folded on the Flop".to_string(),
+            StreetType::Turn => " folded on the Turn".to_string(),
+            StreetType::River => " folded on the River".to_string(),
+            StreetType::Draw => format!(" folded after the {} Draw", ["1st", "2nd", "3rd"].get(street.index as usize).unwrap_or(&"3rd")),
+            StreetType::Third => " folded on the 3rd Street".to_string(),
+            StreetType::Fourth => " folded on the 4th Street".to_string(),
+            StreetType::Fifth => " folded on the 5th Street".to_string(),
+            StreetType::Sixth => " folded on the 6th Street".to_string(),
+            StreetType::Seventh => " folded on the 7th Street".to_string(),
+            StreetType::Showdown => continue,
+        };
+    }
+    let bet = hand.actions().any(|a| matches!(a, Action::Blind(..) | Action::Play(_, _, Some(_))) && by(a));
+    if !bet { folded.push_str(" (didn't bet)"); }
+    folded
+}
+
+fn cards_str(cards: &[Card]) -> String {
+    cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+fn amount_str(c: Currency) -> String {
+    // $0.50, 1500, $1.255 when cents would lose precision
+    let symbol = if c.symbol == NO_CURRENCY { String::new() } else { c.symbol.to_string() };
+    let cents = format!("{:.2}", c.amount);
+    if c.amount.fract() == 0.0 {
+        format!("{}{}", symbol, c.amount as i64)
+    } else if cents.parse::<Float>() == Ok(c.amount) {
+        format!("{}{}", symbol, cents)
+    } else {
+        format!("{}{}", symbol, c.amount)
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let variants = "PokerStars Home Game Hand #208966141596: {Club Name}  Hold'em No Limit (10/20) - 2020/02/08 4:01:19 ET
+Table 'Club Table' 9-max (Play Money) Seat #2 is the button
+Seat 1: pondtree312 (1500 in chips)
+Seat 3: spr1teg (1500 in chips, $5 bounty)
+pondtree312: posts the ante 5
+spr1teg: posts the ante 5
+pondtree312: posts small blind 10
+spr1teg: posts big blind 20
+*** HOLE CARDS ***
+pondtree312: folds
+Uncalled bet (10) returned to spr1teg
+spr1teg collected 30 from pot
+*** SUMMARY ***
+Total pot 30 | Rake 0
+Seat 3: spr1teg (big blind) collected (30)
+
+
+PokerStars Hand #208966141597:  Triple Draw 2-7 Lowball Limit ($0.10/$0.20 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($4 in chips)
+Seat 2: Rifama ($4 in chips)
+Rifama: posts small blind $0.05
+pondtree312: posts big blind $0.10
+*** DEALING HANDS ***
+Dealt to Rifama [7h 2c 8d 5s Kc]
+Rifama: calls $0.05
+pondtree312: checks
+*** FIRST DRAW ***
+Rifama: discards 1 card [Kc]
+Dealt to Rifama [7h 2c 8d 5s] [3d]
+pondtree312: discards 2 cards
+Rifama: bets $0.10
+pondtree312: calls $0.10
+*** SECOND DRAW ***
+Rifama: stands pat
+pondtree312: stands pat
+*** SHOW DOWN ***
+Rifama: shows [7h 2c 8d 5s 3d] (Lo: 8,7,5,3,2)
+Rifama collected $0.38 from pot
+*** SUMMARY ***
+Total pot $0.40 | Rake $0.02
+Seat 2: Rifama (small blind) showed [7h 2c 8d 5s 3d] and won ($0.38) with Lo: 8,7,5,3,2
+
+
+PokerStars Hand #208966141598:  HORSE (Razz Limit, $0.04/$0.08 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 8-max
+Seat 1: pondtree312 ($2 in chips)
+Seat 2: Rifama ($2 in chips)
+pondtree312: posts the ante $0.01
+Rifama: posts the ante $0.01
+*** 3rd STREET ***
+Dealt to pondtree312 [Kh]
+Dealt to Rifama [9s 4d 2c]
+pondtree312: brings in for $0.02
+Rifama: completes it to $0.04
+pondtree312: calls $0.02
+*** 7th STREET ***
+Dealt to Rifama [9s 4d 2c 7h 5d Ah] [3s]
+Rifama: bets $0.08
+pondtree312: folds
+Uncalled bet ($0.08) returned to Rifama
+Rifama collected $0.10 from pot
+*** SUMMARY ***
+Total pot $0.10 | Rake $0
+Seat 2: Rifama collected ($0.10)
+
+
+PokerStars Hand #208966141599:  Omaha Hi/Lo Pot Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #1 is the button
+Seat 1: pondtree312 ($10 in chips)
+Seat 2: Rifama ($50 in chips)
+Rifama: posts small blind $0.25
+pondtree312: posts big blind $0.50
+*** HOLE CARDS ***
+pondtree312: raises $9.50 to $10 and is all-in
+Rifama: calls $9.75
+*** FIRST FLOP *** [8d 3c Ad]
+*** FIRST TURN *** [8d 3c Ad] [9c]
+*** FIRST RIVER *** [8d 3c Ad 9c] [6d]
+*** SECOND FLOP *** [Kh 3d 4s]
+*** SECOND TURN *** [Kh 3d 4s] [2h]
+*** SECOND RIVER *** [Kh 3d 4s 2h] [Qc]
+*** FIRST SHOW DOWN ***
+pondtree312: shows [Ah 2c 5d Kc] (HI: a pair of Aces; LO: 6,5,3,2,A)
+Rifama: shows [9h 9d Qs Js] (HI: three of a kind, Nines)
+Rifama collected $4.75 from pot
+pondtree312 collected $4.75 from pot
+*** SECOND SHOW DOWN ***
+pondtree312 collected $9.50 from pot
+*** SUMMARY ***
+Total pot $20 | Rake $1 | Jackpot $0.10 | Bingo $0
+Hand was run twice
+FIRST Board [8d 3c Ad 9c 6d]
+SECOND Board [Kh 3d 4s 2h Qc]
+Seat 1: pondtree312 (button) (big blind) showed [Ah 2c 5d Kc] and won ($4.75) with LO: 6,5,3,2,A, and won ($9.50) with HI: a straight, Ace to Five
+Seat 2: Rifama (small blind) showed [9h 9d Qs Js] and won ($4.75) with HI: three of a kind, Nines
+
+
+PokerStars Hand #208966141602:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($5 in chips)
+Seat 2: p2 ($10 in chips)
+Seat 3: p3 ($20 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $19.50 to $20 and is all-in
+p4: calls $20
+p1: calls $4.75 and is all-in
+p2: calls $9.50 and is all-in
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [Qh Qc] (a pair of Queens)
+p4: shows [Jh Jc] (a pair of Jacks)
+p3 collected $14 from side pot-1
+p3 collected $20 from side pot-2
+p1 collected $19 from main pot
+*** SUMMARY ***
+Total pot $55 Main pot $19. Side pot-1 $14. Side pot-2 $20. | Rake $2
+";
+        let examples = ["data/example/pokerstars_example.txt", "data/example/pluribus_example.txt"].map(|f| std::fs::read_to_string(f).unwrap());
+        for data in examples.iter().map(|x| x.as_str()).chain([variants]) {
+            let hands = parse_string(data);
+            let text = render(&hands, data);
+            let again = parse_string(&text);
+            assert_eq!(again.len(), hands.len());
+            assert_eq!(to_json(&again, &text).unwrap(), to_json(&hands, data).unwrap());
+            assert_eq!(render(&again, &text), text);
+        }
+
+        // Folds are written on the street of the player's last action
+        let data = examples[0].as_str();
+        let text = render(&parse_string(data), data);
+        for line in data.lines().filter(|l| l.starts_with("Seat ") && l.contains(" folded ")) {
+            assert!(text.contains(&format!("\n{}\n", line)), "{}", line);
+        }
+
+        let text = render(&parse_string(variants), variants);
+        assert!(text.starts_with("PokerStars Home Game Hand #208966141596: {Club Name}  Hold'em No Limit (10/20)\n"));
+        assert!(text.contains("\nSeat 3: spr1teg (1500 in chips, $5 bounty)\n"));
+        assert!(text.contains("\nRifama: completes it to $0.04\n"));
+        assert!(text.contains("\nRifama: discards 1 card [Kc]\nDealt to Rifama [7h 2c 8d 5s] [3d]\n"));
+        assert!(text.contains("\n*** SECOND FLOP *** [Kh 3d 4s]\n"));
+        assert!(text.contains("\npondtree312: raises $9.50 to $10 and is all-in\n"));
+        assert!(text.contains("\nTotal pot $55 Main pot $19. Side pot-1 $14. Side pot-2 $20. | Rake $2\n"));
+        assert!(text.contains("\np3 collected $14 from side pot-1\n"));
+        assert!(text.contains("\np1 collected $19 from main pot\n"));
+        assert!(text.contains("\nspr1teg collected 30 from pot\n"));
+    }
+
+    #[test]
+    fn round_trip_other_site() {
+        let data = "***** Hand History for Game 19233040011 *****
+$0.25/$0.50 USD NL Texas Hold'em - Saturday, February 08, 04:01:19 ET 2020
+Table Acamar (Real Money)
+Seat 3 is the button
+Total number of players : 3/6
+Seat 1: pondtree312 ( $49.50 USD )
+Seat 2: Rifama ( $50 USD )
+Seat 3: spr1teg ( $50.50 USD )
+pondtree312 posts small blind [$0.25 USD].
+Rifama posts big blind [$0.50 USD].
+** Dealing down cards **
+Dealt to spr1teg [  8s Jd ]
+spr1teg raises [$1.25 USD]
+pondtree312 calls [$1 USD]
+Rifama folds
+** Dealing Flop ** [ 8d, 3c, Ad ]
+pondtree312 checks
+spr1teg bets [$1 USD]
+pondtree312 raises [$3 USD]
+spr1teg calls [$2 USD]
+** Dealing Turn ** [ 9c ]
+pondtree312 checks
+spr1teg checks
+** Dealing River ** [ 6d ]
+pondtree312 checks
+spr1teg checks
+pondtree312 shows [ 5d, Ks ]high card Ace.
+spr1teg shows [ 8s, Jd ]a pair of Eights.
+spr1teg wins $8.85 USD from the main pot with a pair of Eights.
+";
+        let hands = parse_string(data);
+        let text = render(&hands, data);
+        assert!(text.starts_with("PokerStars Hand #19233040011:  NL Texas Hold'em ($0.25/$0.50)\n"));
+        assert!(detect_site(&text).is_some_and(|site| site.name() == "PokerStars"));
+        let again = parse_string(&text);
+        assert_eq!(again.len(), 1);
+        assert_eq!(render(&again, &text), text);
+        let json = to_json(&hands, data).unwrap().replace(r#""site_name":"PartyPoker","site":"Hand History","hand":"Game""#, r#""site_name":"PokerStars","site":"PokerStars","hand":"Hand""#);
+        assert_eq!(to_json(&again, &text).unwrap(), json);
+    }
+}
@@ -0,0 +1,146 @@
+//region Results
+
+// What each player won or lost in a hand, in the currency of the pot.
+// The rake is shared by what the players put in, the winners do not pay it alone.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerResult {
+    pub name: Span,
+    pub invested: Currency,
+    pub collected: Currency,
+    // Received instead of the pot by players who cashed out the hand
+    pub cashed_out: Currency,
+    pub cash_out_fees: Currency,
+    pub rake: Currency,
+    pub net: Currency,
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // Seated players in seat order, players missing from the seats are appended
+    pub fn results(&self, data: &str) -> Vec<PlayerResult> {
+        let symbol = self.summary.pot.symbol;
+        let amount = |v: Float| Currency{symbol, amount: (v * 10000.0).round() / 10000.0};
+
+        let mut state = HandState::new(self, data);
+        state.by_ref().for_each(drop);
+        let total = state.players.iter().map(|p| p.invested).sum::<Float>();
+        let rake = self.summary.rake.map_or(0.0, |r| r.amount);
+
+        let name_span = |name: &str| self.header.players.iter().map(|p| p.name)
+            .chain(self.actions().filter_map(action_player))
+            .find(|s| &data[*s] == name);
+
+        state.players.iter().filter_map(|p| {
+            let mut collected = 0.0;
+            let mut cashed_out = None;
+            let mut fees = 0.0;
+            for a in self.actions() {
+                match a {
+                    Action::CollectedPot(_, n, c, _) if &data[*n] == p.name => collected += c.amount,
+                    Action::CashOut(n, c, fee) if &data[*n] == p.name => {
+                        *cashed_out.get_or_insert(0.0) += c.amount;
+                        fees += fee.amount;
+                    }
+                    Action::PaysCashOutRisk(n, c) if &data[*n] == p.name => fees += c.amount,
+                    _ => {}
+                }
+            }
+            let share = if total > 0.0 { rake * p.invested / total } else { 0.0 };
+            let net = cashed_out.unwrap_or(collected) - p.invested - fees;
+            Some(PlayerResult{
+                name: name_span(p.name)?,
+                invested: amount(p.invested),
+                collected: amount(collected),
+                cashed_out: amount(cashed_out.unwrap_or(0.0)),
+                cash_out_fees: amount(fees),
+                rake: amount(share),
+                net: amount(net),
+            })
+        }).collect()
+    }
+}
+
+fn action_player(a: &Action) -> Option<Span> {
+    match a {
+        Action::Blind(_, name, _) | Action::Play(_, name, _) | Action::Fold(name, _) | Action::UncalledBetReturned(name, _)
+        | Action::CollectedPot(_, name, _, _) | Action::CashOut(name, _, _) | Action::PaysCashOutRisk(name, _) => Some(*name),
+        _ => None,
+    }
+}
+
+impl Json for PlayerResult {
+    fn serialize(&self, f: &mut JsonFormatter) -> JsonResult {
+        f.object()
+            .entry(JSON_KEY_NAME, &self.name)
+            .entry("invested", &self.invested)
+            .entry("collected", &self.collected)
+            .entry("cashed_out", &self.cashed_out)
+            .entry("cash_out_fees", &self.cash_out_fees)
+            .entry("rake", &self.rake)
+            .entry("net", &self.net)
+            .finish()
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn net_results() {
+        let data = "PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: pondtree312 ($49.50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50.50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+spr1teg: raises $0.75 to $1.25
+pondtree312: calls $1
+Rifama: folds
+*** FLOP *** [8d 3c Ad]
+pondtree312: bets $2
+spr1teg: raises $4 to $6
+pondtree312: raises $42.25 to $48.25 and is all-in
+spr1teg: calls $42.25
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+pondtree312: shows [5d Ks] (high card Ace)
+spr1teg: shows [8s Jd] (a pair of Eights)
+pondtree312 cashed out the hand for $40 | Cash Out Fee $0.40
+spr1teg collected $99 from pot
+*** SUMMARY ***
+Total pot $99.50 | Rake $0.50
+";
+        let hands = parse_string(data);
+        let results = hands[0].results(data);
+        assert_eq!(results.iter().map(|r| &data[r.name]).collect::<Vec<_>>(), ["pondtree312", "Rifama", "spr1teg"]);
+
+        let cashed = &results[0];
+        assert_eq!(cashed.invested.amount, 49.5);
+        assert_eq!(cashed.cashed_out.amount, 40.0);
+        assert_eq!(cashed.cash_out_fees.amount, 0.4);
+        assert_eq!(cashed.net.amount, -9.9);
+
+        assert_eq!(results[1].net.amount, -0.5);
+        assert_eq!(results[1].rake.amount, 0.0025);
+
+        let winner = &results[2];
+        assert_eq!(winner.collected.amount, 99.0);
+        assert_eq!(winner.net.amount, 49.5);
+        assert_eq!(winner.rake.amount, 0.2487);
+        assert_eq!(winner.net.symbol, '$');
+
+        let json = to_json_with(&hands, data, JsonOptions{results: true, ..Default::default()}).unwrap();
+        assert!(json.contains(r#""results":[{"name":"pondtree312","invested":["$",49.5],"collected":["$",0],"cashed_out":["$",40],"cash_out_fees":["$",0.4],"rake":["$",0.2487],"net":["$",-9.9]}"#));
+        assert!(!to_json(&hands, data).unwrap().contains("\"results\""));
+    }
+}
@@ -0,0 +1,364 @@
+//region HandState
+
+// Replays the actions of a hand in order and keeps the betting state after each one:
+//
+// let mut state = HandState::new(&hand, data);
+// while let Some(action) = state.next() {
+//     println!("{:?} pot {} to call {}", action, state.pot, state.bet);
+// }
+//
+// Raises are replayed to their total, "raises $0.75 to $1.25" commits $1.25 over the street.
+
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct PlayerState<'d> {
+    pub name: &'d str,
+    pub seat: u8,
+    // False for players who act without being listed in the seats, their seat and stack are unknown
+    pub seated: bool,
+    pub stack: Float,
+    // Live chips of the current street, antes and dead blinds excluded
+    pub committed: Float,
+    pub invested: Float,
+    // Players sitting out start folded
+    pub folded: bool,
+    pub all_in: bool,
+    acted: bool,
+}
+
+pub struct HandState<'h, 'd> {
+    hand: &'h Hand,
+    data: &'d str,
+    pub players: Vec<PlayerState<'d>>,
+    pub pot: Float,
+    // Highest commitment of the street
+    pub bet: Float,
+    last_raise: Float,
+    section: usize,
+    position: usize,
+    to_act: Option<usize>,
+}
+
+#[allow(dead_code)]
+impl<'h, 'd> HandState<'h, 'd> {
+    pub fn new(hand: &'h Hand, data: &'d str) -> Self {
+        let mut seats = hand.header.players.iter().collect::<Vec<_>>();
+        seats.sort_by_key(|p| p.seat);
+        let sitting_out = |name: &str| hand.header.actions.iter()
+            .any(|a| matches!(a, Action::Event(Event::Sitout, n) if &data[*n] == name));
+        let players = seats.iter().map(|p| PlayerState{
+            name: &data[p.name], seat: p.seat, seated: true, stack: p.chips.amount, committed: 0.0, invested: 0.0,
+            folded: sitting_out(&data[p.name]), all_in: false, acted: false,
+        }).collect();
+
+        let mut state = Self{hand, data, players, pot: 0.0, bet: 0.0, last_raise: hand.header.info.buy_in_max.amount, section: 0, position: 0, to_act: None};
+        state.to_act = state.next_to_act(state.button());
+        state
+    }
+
+    // None before the first street, while the blinds are posted and the hole cards are played
+    pub fn street(&self) -> Option<&'h Street> {
+        self.section.checked_sub(2).and_then(|i| self.hand.streets.get(i))
+    }
+
+    pub fn player(&self, name: &str) -> Option<&PlayerState<'d>> {
+        self.players.iter().find(|p| p.name == name)
+    }
+
+    pub fn to_call(&self, name: &str) -> Float {
+        self.player(name).map_or(0.0, |p| (self.bet - p.committed).min(p.stack).max(0.0))
+    }
+
+    // Smallest total a raise has to reach
+    pub fn min_raise(&self) -> Float {
+        self.bet + self.last_raise
+    }
+
+    pub fn active(&self) -> impl Iterator<Item=&PlayerState<'d>> {
+        self.players.iter().filter(|p| !p.folded && !p.all_in)
+    }
+
+    pub fn all_in(&self) -> impl Iterator<Item=&PlayerState<'d>> {
+        self.players.iter().filter(|p| !p.folded && p.all_in)
+    }
+
+    // None once the betting of the street is complete
+    pub fn to_act(&self) -> Option<&PlayerState<'d>> {
+        self.to_act.map(|i| &self.players[i])
+    }
+
+    fn section(&self, section: usize) -> Option<&'h ActionVec> {
+        match section {
+            0 => Some(&self.hand.header.actions),
+            1 => Some(&self.hand.hole_cards.actions),
+            _ => self.hand.streets.get(section - 2).map(|s| &s.actions),
+        }
+    }
+
+    fn index(&mut self, name: &Span) -> usize {
+        let name = &self.data[*name];
+        match self.players.iter().position(|p| p.name == name) {
+            Some(i) => i,
+            None => {
+                // Players missing from the seat list still have their chips counted, they never run out of chips
+                self.players.push(PlayerState{name, seat: 0, seated: false, stack: Float::INFINITY, committed: 0.0, invested: 0.0, folded: false, all_in: false, acted: false});
+                self.players.len() - 1
+            }
+        }
+    }
+
+    // Index of the last player before the first seat after the button
+    fn button(&self) -> usize {
+        let button = self.hand.header.table.button;
+        self.players.iter().position(|p| p.seat > button).unwrap_or(0).checked_sub(1).unwrap_or(self.players.len().saturating_sub(1))
+    }
+
+    fn next_to_act(&self, from: usize) -> Option<usize> {
+        if self.players.iter().filter(|p| !p.folded).count() < 2 { return None; }
+        let n = self.players.len();
+        (1..=n).map(|i| (from + i) % n).find(|i| {
+            let p = &self.players[*i];
+            !p.folded && !p.all_in && (!p.acted || p.committed < self.bet)
+        }).filter(|i| {
+            // A lone player left to act only has to match the all-ins
+            self.active().count() > 1 || self.players[*i].committed < self.bet
+        })
+    }
+
+    fn start_street(&mut self, street: &Street) {
+        for p in self.players.iter_mut() {
+            p.committed = 0.0;
+            p.acted = false;
+        }
+        self.bet = 0.0;
+        self.last_raise = self.hand.header.info.buy_in_max.amount;
+        self.to_act = match street.t {
+            StreetType::Showdown => None,
+            // The bring in and the best showing hand open stud streets, the log tells who it was
+            StreetType::Third | StreetType::Fourth | StreetType::Fifth | StreetType::Sixth | StreetType::Seventh => {
+                let first = street.actions.iter().find_map(|a| match a {
+                    Action::Play(_, name, _) | Action::Fold(name, _) | Action::Blind(BlindType::BringIn, name, _) => Some(&self.data[*name]),
+                    _ => None,
+                });
+                self.players.iter().position(|p| Some(p.name) == first)
+            }
+            _ => self.next_to_act(self.button()),
+        };
+    }
+
+    fn put(&mut self, i: usize, added: Float, live: Float, all_in: bool) {
+        let p = &mut self.players[i];
+        p.stack -= added;
+        p.invested += added;
+        p.committed += live;
+        p.all_in |= all_in || p.stack <= 0.005;
+        self.pot += added;
+        self.bet = self.bet.max(p.committed);
+    }
+
+    fn apply(&mut self, action: &Action) {
+        let i = match action {
+            Action::Blind(t, name, amount) => {
+                let i = self.index(name);
+                let live = match t {
+                    BlindType::Ante => 0.0,
+                    // The small blind part is dead
+                    BlindType::SmallAndBig => amount.amount - self.hand.header.info.buy_in_min.amount,
+                    _ => amount.amount,
+                };
+                self.put(i, amount.amount, live, false);
+                i
+            }
+            Action::Play(t, name, d) => {
+                let i = self.index(name);
+                let before = self.bet;
+                match (t, d) {
+                    (Play::Call | Play::Bet, Some(d)) => self.put(i, d.bet.amount, d.bet.amount, d.all_in),
+                    (Play::Raise, Some(d)) => {
+                        let to = d.bet_to.map_or(before + d.bet.amount, |to| to.amount);
+                        let added = to - self.players[i].committed;
+                        self.put(i, added, added, d.all_in);
+                    }
+                    _ => {}
+                }
+                if self.bet > before {
+                    // Short all-in raises do not change the minimum raise
+                    self.last_raise = self.last_raise.max(self.bet - before);
+                    for p in self.players.iter_mut() { p.acted = false; }
+                }
+                self.players[i].acted = true;
+                i
+            }
+            Action::Fold(name, _) => {
+                let i = self.index(name);
+                self.players[i].folded = true;
+                i
+            }
+            Action::UncalledBetReturned(name, amount) => {
+                let i = self.index(name);
+                self.put(i, -amount.amount, -amount.amount, false);
+                self.bet = self.players.iter().map(|p| p.committed).fold(0.0, Float::max);
+                i
+            }
+            Action::CollectedPot(_, name, amount, _) => {
+                let i = self.index(name);
+                self.players[i].stack += amount.amount;
+                return;
+            }
+            _ => return,
+        };
+        self.to_act = self.next_to_act(i);
+    }
+}
+
+impl<'h> Iterator for HandState<'h, '_> {
+    type Item = &'h Action;
+
+    fn next(&mut self) -> Option<&'h Action> {
+        loop {
+            let actions = self.section(self.section)?;
+            if let Some(action) = actions.get(self.position) {
+                self.position += 1;
+                self.apply(action);
+                return Some(action);
+            }
+            self.section += 1;
+            self.position = 0;
+            if let Some(street) = self.street() {
+                self.start_street(street);
+            }
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Hand {
+    // Chips each player put in the pot, antes included and uncalled bets excluded
+    pub fn invested<'d>(&self, data: &'d str) -> HashMap<&'d str, Float> {
+        let mut state = HandState::new(self, data);
+        state.by_ref().for_each(drop);
+        state.players.iter().map(|p| (p.name, p.invested)).collect()
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay() {
+        let data = "PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: pondtree312 ($49.50 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50.50 in chips)
+Seat 5: yumeko0001 ($20 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+yumeko0001: sits out
+*** HOLE CARDS ***
+spr1teg: raises $0.75 to $1.25
+pondtree312: calls $1
+Rifama: folds
+*** FLOP *** [8d 3c Ad]
+pondtree312: bets $2
+spr1teg: raises $4 to $6
+pondtree312: raises $42.25 to $48.25 and is all-in
+spr1teg: calls $42.25
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+pondtree312: shows [5d Ks] (high card Ace)
+spr1teg: shows [8s Jd] (a pair of Eights)
+spr1teg collected $99 from pot
+*** SUMMARY ***
+Total pot $99.50 | Rake $0.50
+";
+        let hands = parse_string(data);
+        let mut state = HandState::new(&hands[0], data);
+        assert_eq!(state.to_act().unwrap().name, "pondtree312");
+
+        state.nth(2);
+        assert!(state.street().is_none());
+        assert!(state.player("yumeko0001").unwrap().folded);
+        assert_eq!(state.to_act().unwrap().name, "spr1teg");
+        assert_eq!(state.to_call("spr1teg"), 0.5);
+        assert_eq!(state.min_raise(), 1.0);
+
+        // The raise commits its total, not the increment
+        state.next();
+        assert_eq!(state.player("spr1teg").unwrap().committed, 1.25);
+        assert_eq!(state.pot, 2.0);
+        assert_eq!(state.min_raise(), 2.0);
+        assert_eq!(state.to_act().unwrap().name, "pondtree312");
+
+        state.nth(1);
+        assert!(state.to_act().is_none());
+        assert_eq!(state.pot, 3.0);
+
+        // Commitments start over on the flop
+        state.next();
+        assert!(matches!(state.street().unwrap().t, StreetType::Flop));
+        assert_eq!(state.player("spr1teg").unwrap().committed, 0.0);
+        assert_eq!(state.bet, 2.0);
+        assert_eq!(state.to_act().unwrap().name, "spr1teg");
+        assert_eq!(state.min_raise(), 4.0);
+
+        state.nth(1);
+        let all_in = state.player("pondtree312").unwrap();
+        assert!(all_in.all_in);
+        assert_eq!(all_in.stack, 0.0);
+        assert_eq!(all_in.invested, 49.5);
+        assert_eq!(state.to_call("spr1teg"), 42.25);
+        assert_eq!(state.active().count(), 1);
+        assert_eq!(state.all_in().count(), 1);
+
+        state.next();
+        assert!(state.to_act().is_none());
+        assert_eq!(state.pot, 99.5);
+
+        for _ in state.by_ref() {}
+        assert!(state.to_act().is_none());
+        assert_eq!(state.player("spr1teg").unwrap().stack, 100.0);
+        assert_eq!(hands[0].invested(data)["spr1teg"], 49.5);
+    }
+
+    #[test]
+    fn unseated_player() {
+        let data = "PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #2 is the button
+Seat 1: pondtree312 ($49.50 in chips)
+Seat 2: Rifama ($50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+spr1teg: raises $0.75 to $1.25
+pondtree312: folds
+Rifama: calls $0.75
+*** FLOP *** [8d 3c Ad]
+Rifama: checks
+spr1teg: bets $2
+Rifama: folds
+Uncalled bet ($2) returned to spr1teg
+spr1teg collected $2.50 from pot
+*** SUMMARY ***
+Total pot $2.75 | Rake $0
+";
+        let hands = parse_string(data);
+        let mut state = HandState::new(&hands[0], data);
+        state.nth(2);
+        let unseated = state.player("spr1teg").unwrap();
+        assert!(!unseated.seated);
+        assert!(!unseated.all_in);
+        assert_eq!(unseated.invested, 1.25);
+        assert!(state.player("Rifama").unwrap().seated);
+
+        state.nth(2);
+        assert_eq!(state.to_act().unwrap().name, "spr1teg");
+        assert_eq!(hands[0].invested(data)["spr1teg"], 1.25);
+    }
+}
@@ -0,0 +1,154 @@
+//region Validate
+
+// Chip conservation checks, corrupt or misparsed hands come out as messages instead of wrong numbers:
+//
+// Hand #208966141595: collected $2.75 plus rake $0.15 is $2.90, the total pot is $3
+
+use super::*;
+
+// Cents are rounded differently between the sites and split pots
+const TOLERANCE: Float = 0.011;
+
+#[derive(Debug, Clone)]
+pub struct Violation<'d> {
+    pub hand_id: &'d str,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hand #{}: {}", self.hand_id, self.message)
+    }
+}
+
+#[allow(dead_code)]
+impl Hand {
+    pub fn validate<'d>(&self, data: &'d str) -> Vec<Violation<'d>> {
+        let mut messages = Vec::new();
+        let summary = &self.summary;
+        let pot = summary.pot;
+        let amount = |v: Float| Currency{symbol: pot.symbol, amount: v};
+        let differs = |a: Float, b: Float| (a - b).abs() > TOLERANCE;
+
+        let mut state = HandState::new(self, data);
+        state.by_ref().for_each(drop);
+        if differs(state.pot, pot.amount) {
+            messages.push(format!("blinds and bets less uncalled bets are {}, the total pot is {}", show(amount(state.pot)), show(pot)));
+        }
+
+        let collected = self.actions()
+            .filter_map(|a| match a { Action::CollectedPot(_, _, amount, _) => Some(amount.amount), _ => None })
+            .sum::<Float>();
+        let rake = summary.rake.map_or(0.0, |r| r.amount);
+        // Jackpots and the other fees are taken from the pot like the rake
+        let fees = summary.jackpot.map_or(0.0, |j| j.amount) + summary.fees.iter().map(|(_, f)| f.amount).sum::<Float>();
+        if differs(collected + rake + fees, pot.amount) {
+            let taken = if fees > 0.0 { format!("rake and fees {}", show(amount(rake + fees))) } else { format!("rake {}", show(amount(rake))) };
+            messages.push(format!("collected {} plus {} is {}, the total pot is {}", show(amount(collected)), taken, show(amount(collected + rake + fees)), show(pot)));
+        }
+
+        // Pots without a side pot only list the total, PokerStars lists the pots after the rake is taken
+        let (main, side) = (summary.main_pot.amount, summary.side_pot.amount);
+        if side != 0.0 && differs(main + side, pot.amount) && differs(main + side + rake, pot.amount) {
+            messages.push(format!("main pot {} plus side pots {} is {}, the total pot is {}", show(summary.main_pot), show(summary.side_pot), show(amount(main + side)), show(pot)));
+        }
+
+        for p in &self.header.players {
+            let invested = state.player(&data[p.name]).map_or(0.0, |s| s.invested);
+            if invested - p.chips.amount > TOLERANCE {
+                messages.push(format!("{} put in {} with a stack of {}", &data[p.name], show(amount(invested)), show(p.chips)));
+            }
+        }
+        for s in state.players.iter().filter(|s| !s.seated) {
+            messages.push(format!("{} put in {} without a seat", s.name, show(amount(s.invested))));
+        }
+
+        let hand_id = &data[self.header.info.id];
+        messages.into_iter().map(|message| Violation{hand_id, message}).collect()
+    }
+}
+
+fn show(c: Currency) -> String {
+    // $2.90, 1500
+    let symbol = if c.symbol == NO_CURRENCY { String::new() } else { c.symbol.to_string() };
+    if c.amount.fract() == 0.0 { format!("{}{}", symbol, c.amount as i64) } else { format!("{}{:.2}", symbol, c.amount) }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_add_up() {
+        for file in ["data/example/pokerstars_example.txt", "data/example/pluribus_example.txt"] {
+            let data = std::fs::read_to_string(file).unwrap();
+            let violations = parse_string(&data).iter().flat_map(|h| h.validate(&data)).map(|v| v.to_string()).collect::<Vec<_>>();
+            assert!(violations.is_empty(), "{:?}", violations);
+        }
+    }
+
+    #[test]
+    fn violations() {
+        let data = "PokerStars Hand #208966141595:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #3 is the button
+Seat 1: pondtree312 ($1 in chips)
+Seat 2: Rifama ($50 in chips)
+Seat 3: spr1teg ($50.50 in chips)
+pondtree312: posts small blind $0.25
+Rifama: posts big blind $0.50
+*** HOLE CARDS ***
+spr1teg: raises $0.75 to $1.25
+pondtree312: calls $1
+Rifama: folds
+*** FLOP *** [8d 3c Ad]
+pondtree312: checks
+spr1teg: checks
+*** SHOW DOWN ***
+spr1teg collected $2.75 from pot
+*** SUMMARY ***
+Total pot $3 Main pot $2. Side pot $0.50. | Rake $0.15
+";
+        let hands = parse_string(data);
+        let violations = hands[0].validate(data).iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(violations, [
+            "Hand #208966141595: collected $2.75 plus rake $0.15 is $2.90, the total pot is $3",
+            "Hand #208966141595: main pot $2 plus side pots $0.50 is $2.50, the total pot is $3",
+            "Hand #208966141595: pondtree312 put in $1.25 with a stack of $1",
+        ]);
+    }
+
+    #[test]
+    fn side_pot_after_rake() {
+        let data = "PokerStars Hand #208966141600:  Hold'em No Limit ($0.25/$0.50 USD) - 2020/02/08 4:01:19 ET
+Table 'Acamar V' 6-max Seat #4 is the button
+Seat 1: p1 ($10 in chips)
+Seat 2: p2 ($30 in chips)
+Seat 3: p3 ($50 in chips)
+Seat 4: p4 ($50 in chips)
+p1: posts small blind $0.25
+p2: posts big blind $0.50
+*** HOLE CARDS ***
+p3: raises $4.50 to $5
+p4: folds
+p1: raises $4.75 to $10 and is all-in
+p2: raises $20 to $30 and is all-in
+p3: calls $25
+*** FLOP *** [8d 3c Ad]
+*** TURN *** [8d 3c Ad] [9c]
+*** RIVER *** [8d 3c Ad 9c] [6d]
+*** SHOW DOWN ***
+p1: shows [Ah Ac] (three of a kind, Aces)
+p2: shows [Kh Kc] (a pair of Kings)
+p3: shows [8s 8h] (three of a kind, Eights)
+p3 collected $39 from side pot
+p1 collected $29 from main pot
+*** SUMMARY ***
+Total pot $70 Main pot $29. Side pot $39. | Rake $2
+";
+        let hands = parse_string(data);
+        assert!(hands[0].validate(data).is_empty());
+    }
+}
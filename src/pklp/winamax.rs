@@ -0,0 +1,232 @@
+//region Winamax
+
+// Winamax Poker - CashGame - HandId: #1234567-89-1581134479 - Holdem no limit (0.25€/0.50€) - 2020/02/08 04:01:19 UTC
+// Table: 'Nice 05' 6-max (real money) Seat #3 is the button
+// Seat 1: pondtree312 (49.50€)
+// *** ANTE/BLINDS ***
+// pondtree312 posts small blind 0.25€
+// Dealt to spr1teg [8s Jd]
+// *** PRE-FLOP ***
+// spr1teg raises 0.75€ to 1.25€
+// *** TURN *** [8d 3c Ad][9c]
+// spr1teg collected 2.85€ from pot
+// Total pot 3€ | Rake 0.15€
+
+use super::*;
+
+pub struct Winamax;
+
+impl SiteParser for Winamax {
+    fn name(&self) -> &'static str { "Winamax" }
+
+    fn detect(&self, head: &str) -> bool {
+        head.lines().any(is_hand_header)
+    }
+
+    fn parse(&self, data: &str) -> Vec<Hand> {
+        hand_blocks(data, is_hand_header).iter().filter_map(|lines| parse_hand(lines, data)).collect()
+    }
+
+    fn count_hands(&self, data: &str) -> usize {
+        data.lines().filter(|l| is_hand_header(l)).count()
+    }
+}
+
+fn is_hand_header(line: &str) -> bool {
+    line.trim_start_matches('\u{feff}').starts_with("Winamax Poker - ")
+}
+
+fn parse_hand(lines: &[&str], data: &str) -> Option<Hand> {
+    let span = |s: &str| Span::from_str_slice(s, data);
+    let mut lines = lines.iter().copied().peekable();
+
+    // Winamax Poker - Tournament "Freeroll" buyIn: 0€ + 0€ level: 6 - HandId: #1-2-3 - Holdem no limit (10/50/100) - 2020/02/08 04:01:19 UTC
+    let title = lines.next()?.trim_start_matches('\u{feff}');
+    let site = title.split(' ').next()?;
+    let hand_start = title.find("HandId: #")?;
+    let hand = &title[hand_start..hand_start + "HandId".len()];
+    let rest = &title[hand_start + "HandId: #".len()..];
+    let (id, rest) = rest.split_once(" - ")?;
+    let game_type = &rest[..rest.find(" (")?];
+    let stakes = rest.between('(', ')');
+    // Tournament stakes start with the ante
+    let stakes = stakes.rsplitn(3, '/').take(2).collect::<Vec<_>>();
+    let (buy_in_min, buy_in_max) = (parse_currency_any(stakes.get(1)?)?, parse_currency_any(stakes.first()?)?);
+
+    // Table: 'Nice 05' 6-max (real money) Seat #3 is the button
+    let table_line = lines.next()?;
+    let table_name = table_line.between('\'', '\'');
+    let max_players = table_line.find("-max").and_then(|end| table_line[..end].rsplit(' ').next()).and_then(parse_integer).map_or(0, |(v, _)| v as u8);
+    let button = parse_button(table_line).unwrap_or(0);
+    let play_money = table_line.contains("(play money)");
+
+    let mut b = HandBuilder::default();
+    while lines.peek().is_some_and(|line| b.seat(line, data)) {
+        lines.next();
+    }
+
+    let info = HandInfo{
+        site_name: "Winamax", site: span(site), hand: span(hand), id: span(id),
+        is_zoom: false, home_game_club: None, mixed_game: None,
+        game_type: span(game_type), buy_in_min, buy_in_max,
+    };
+    let table = Table{name: span(table_name), max_players, button, play_money};
+    let header = Header{info, table, players: PlayerVec::new(), actions: ActionVec::new()};
+
+    let mut rake = None;
+    let mut in_summary = false;
+    for line in lines {
+        if let Some(section) = line.strip_prefix("*** ") {
+            let section = section.prefix_str(" ***");
+            match section {
+                "ANTE/BLINDS" => continue,
+                "PRE-FLOP" => { b.hole_cards(); }
+                "FLOP" | "TURN" | "RIVER" => {
+                    let t = match section { "FLOP" => StreetType::Flop, "TURN" => StreetType::Turn, _ => StreetType::River };
+                    b.deal_board(t, parse_cards(line.rbetween('[', ']')));
+                }
+                "SHOW DOWN" => b.showdown(),
+                "SUMMARY" => in_summary = true,
+                _ => {}
+            }
+        }
+        else if in_summary {
+            if let Some(total) = line.strip_prefix("Total pot ") {
+                // Total pot 9€ | Rake 0.15€, Total pot 1€ | No rake
+                b.total = total.split(' ').next().and_then(parse_currency_any);
+                rake = total.split(" | ").find_map(|f| f.strip_prefix("Rake ")).and_then(parse_currency_any);
+            }
+        }
+        else if let Some((name, rest)) = b.player(line, data) {
+            parse_action(&mut b, span(name), name, rest);
+        }
+        else if !b.dealt(line, data) {
+            // Uncalled bet (1€) returned to pondtree312
+            b.uncalled(line, data);
+        }
+    }
+
+    Some(b.finish(header, rake, data))
+}
+
+fn parse_action<'a>(b: &mut HandBuilder<'a>, span: Span, name: &'a str, rest: &str) {
+    // Amounts follow the verb: "raises 0.75€ to 1.25€ and is all-in"
+    let amount = |after: &str| after.split(' ').next().and_then(parse_currency_any);
+    let all_in = rest.ends_with(" and is all-in");
+    if let Some(blind) = rest.strip_prefix("posts ") {
+        let t = if blind.starts_with("small blind") && blind.contains("big blind") {
+            BlindType::SmallAndBig
+        } else if blind.starts_with("small blind") {
+            BlindType::Small
+        } else if blind.starts_with("ante") {
+            BlindType::Ante
+        } else {
+            BlindType::Big
+        };
+        if let Some(amount) = blind.rsplit(' ').next().and_then(parse_currency_any) { b.blind(name, span, t, amount); }
+    }
+    else if let Some(added) = rest.strip_prefix("calls ").or_else(|| rest.strip_prefix("bets ")) {
+        if let Some(amount) = amount(added) { b.play(name, span, amount, all_in); }
+    }
+    else if rest.starts_with("raises ") {
+        if let Some(to) = rest.split_once(" to ").and_then(|(_, to)| amount(to)) { b.raise_to(name, span, to, all_in); }
+    }
+    else if let Some(won) = rest.strip_prefix("collected ") {
+        // collected 2.85€ from pot, collected 1€ from side pot 1
+        let pot = parse_pot(won);
+        if let Some(amount) = amount(won) {
+            b.push(Action::CollectedPot(pot, span, amount, None));
+        }
+    }
+    else {
+        b.common_action(span, rest);
+    }
+}
+
+//endregion
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_winamax_hand() {
+        let data = "Winamax Poker - CashGame - HandId: #1234567-89-1581134479 - Holdem no limit (0.25€/0.50€) - 2020/02/08 04:01:19 UTC
+Table: 'Nice 05' 6-max (real money) Seat #3 is the button
+Seat 1: pondtree312 (49.50€)
+Seat 2: Rifama (50€)
+Seat 3: spr1teg (50.50€)
+*** ANTE/BLINDS ***
+pondtree312 posts small blind 0.25€
+Rifama posts big blind 0.50€
+Dealt to spr1teg [8s Jd]
+*** PRE-FLOP ***
+spr1teg raises 0.75€ to 1.25€
+pondtree312 calls 1€
+Rifama folds
+*** FLOP *** [8d 3c Ad]
+pondtree312 checks
+spr1teg bets 1€
+pondtree312 raises 2€ to 3€
+spr1teg calls 2€
+*** TURN *** [8d 3c Ad][9c]
+pondtree312 checks
+spr1teg checks
+*** RIVER *** [8d 3c Ad 9c][6d]
+pondtree312 checks
+spr1teg checks
+*** SHOW DOWN ***
+pondtree312 shows [5d Ks] (High card : Ace)
+spr1teg shows [8s Jd] (One pair : 8)
+spr1teg collected 8.85€ from pot
+*** SUMMARY ***
+Total pot 9€ | Rake 0.15€
+Board: [8d 3c Ad 9c 6d]
+Seat 3: spr1teg (button) showed [8s Jd] and won 8.85€ with One pair : 8
+
+Winamax Poker - CashGame - HandId: #1234567-90-1581134480 - Holdem no limit (0.25€/0.50€) - 2020/02/08 04:02:19 UTC
+Table: 'Nice 05' 6-max (real money) Seat #1 is the button
+Seat 1: pondtree312 (50€)
+Seat 2: Rifama (50€)
+*** ANTE/BLINDS ***
+pondtree312 posts small blind 0.25€
+Rifama posts big blind 0.50€
+*** PRE-FLOP ***
+pondtree312 raises 1€ to 1.50€
+Rifama folds
+Uncalled bet (1€) returned to pondtree312
+pondtree312 collected 1€ from pot
+*** SUMMARY ***
+Total pot 1€ | No rake
+Seat 1: pondtree312 (button) won 1€
+";
+        assert!(detect_site(data).is_some_and(|site| site.name() == "Winamax"));
+        let hands = parse_string(data);
+        assert_eq!(hands.len(), 2);
+        let hand = &hands[0];
+        assert_eq!(&data[hand.header.info.id], "1234567-89-1581134479");
+        assert_eq!(&data[hand.header.info.game_type], "Holdem no limit");
+        assert_eq!(hand.header.info.buy_in_min.symbol, '€');
+        assert_eq!(hand.header.info.buy_in_max.amount, 0.5);
+        assert_eq!(&data[hand.header.table.name], "Nice 05");
+        assert_eq!(hand.header.table.max_players, 6);
+        assert_eq!(hand.header.table.button, 3);
+        assert_eq!(hand.header.players[0].chips.amount, 49.5);
+        assert_eq!(hand.header.actions.len(), 2);
+        assert_eq!(&data[hand.hero().unwrap()], "spr1teg");
+
+        assert!(matches!(hand.hole_cards.actions[0], Action::Play(Play::Raise, _, Some(ActionData{bet_to: Some(Currency{amount, symbol: '€'}), ..})) if amount == 1.25));
+        assert_eq!(hand.streets.len(), 4);
+        assert_eq!(hand.streets[1].cards.as_ref().unwrap().len(), 3);
+        assert!(matches!(hand.streets[3].actions[2], Action::CollectedPot(Pot::Main, _, Currency{amount, ..}, None) if amount == 8.85));
+        assert_eq!(hand.summary.rake.unwrap().amount, 0.15);
+        assert_eq!(hand.summary.pot.amount, 9.0);
+        assert_eq!(hand.summary.boards[0].len(), 5);
+
+        let walk = &hands[1];
+        assert!(matches!(walk.hole_cards.actions[2], Action::UncalledBetReturned(name, Currency{amount, ..}) if &data[name] == "pondtree312" && amount == 1.0));
+        assert_eq!(walk.summary.pot.amount, 1.0);
+        assert!(walk.summary.rake.is_none());
+    }
+}